@@ -1,13 +1,31 @@
 use opener;
+use std::collections::HashMap;
 use std::error;
+use std::fs::File;
+use std::io::{self, BufReader, ErrorKind};
 
+use crate::audit_log::{
+    format_timestamp, AuditEntryKind, AuditLog, AuditMethod, AuditRecord, AuditStatus,
+};
+use crate::delete_manager::{
+    restore_from_trash, DeleteFailure, DeleteManager, DeleteOutcome, DeleteTask,
+};
+use crate::export::read_ncdu;
 use crate::fps_counter::FPSCounter;
-use crate::fs::{delete_file, delete_folder, DataStore, DataStoreKey, FolderEntryType, SortBy};
+use crate::fs::{
+    hard_link_id, DataStore, DataStoreKey, ExcludeFilter, Folder, FolderEntry, FolderEntryType,
+    GitFilter, GitStatus, ProtectFilter, ScanOptions, SizeMode, SortBy,
+};
+use crate::scan_cache::{cache_file_path, CacheKey, CacheValidator, ScanCache, ValidationEvent};
+use crate::snapshot::{diff_snapshots, Snapshot};
 use crate::spinner::Spinner;
 use crate::task_manager::TaskManager;
+#[cfg(feature = "watch")]
+use crate::watcher::FsWatcher;
 use std::path::{Path, PathBuf};
+use std::time::SystemTime;
 
-use crate::config::{InitConfig, UIConfig};
+use crate::config::{ConfigError, InitConfig, UIConfig};
 use std::env;
 
 use crate::logger::Logger;
@@ -16,6 +34,7 @@ use crate::logger::Logger;
 pub type AppResult<T> = std::result::Result<T, Box<dyn error::Error>>;
 
 enum DiffKind {
+    Add,
     Subtract,
 }
 
@@ -28,6 +47,31 @@ pub struct App<S: DataStore<DataStoreKey>> {
     pub running: bool,
     /// Task manager for async jobs
     pub task_manager: TaskManager<S>,
+    /// Removes entries in background
+    pub delete_manager: DeleteManager,
+    /// Entries of the last deletion which failed, shown until dismissed
+    pub delete_failures: Vec<DeleteFailure>,
+    /// Entries which would have been removed in dry-run mode
+    pub dry_run_deletions: Vec<DeleteTask>,
+    /// Batches of entries moved to trash, the last one is restored on undo
+    pub deletion_history: Vec<Vec<DeleteTask>>,
+    /// Entries moved to trash by the running batch
+    trashed_batch: Vec<DeleteTask>,
+    /// Every deletion is recorded here
+    pub audit_log: Option<AuditLog>,
+    /// Reports changes made under scanned root after the scan
+    #[cfg(feature = "watch")]
+    pub watcher: Option<FsWatcher>,
+    /// Folder of scan caches, set when caching is enabled
+    pub cache_dir: Option<PathBuf>,
+    /// Compares cached tree with the disk
+    pub cache_validator: Option<CacheValidator>,
+    /// Path the last scan was started from
+    scan_root: PathBuf,
+    /// Selected row of the largest files view
+    pub largest_cursor: usize,
+    /// Selected row of the error list
+    pub errors_cursor: usize,
     /// Store for filesystem data
     pub store: S,
     /// Debug logger
@@ -39,11 +83,12 @@ pub struct App<S: DataStore<DataStoreKey>> {
 }
 
 impl<S: DataStore<DataStoreKey>> App<S> {
-    /// Constructs a new instance of [`App`].
-    pub fn new(config: InitConfig) -> Self {
-        let current_path = match config.file_path {
+    /// Constructs a new instance of [`App`]. Scan root is canonicalized, so protected paths
+    /// are matched however it was written
+    pub fn new(config: InitConfig) -> Result<Self, ConfigError> {
+        let current_path = match &config.file_path {
             Some(path) => {
-                let path_buf = PathBuf::from(&path);
+                let path_buf = PathBuf::from(path);
                 if path_buf.is_absolute() {
                     path_buf
                 } else {
@@ -53,18 +98,37 @@ impl<S: DataStore<DataStoreKey>> App<S> {
             }
             None => env::current_dir().unwrap(),
         };
+        let current_path = current_path.canonicalize().unwrap_or(current_path);
+
+        let ui_config = UIConfig::new(&config);
+        let scan_options = ScanOptions {
+            size_mode: ui_config.size_mode.clone(),
+            one_file_system: config.one_file_system.unwrap_or(false),
+            exclude: ExcludeFilter::new(&config.exclude)?,
+            protect: ProtectFilter::new(
+                &config.protect,
+                env::var_os("HOME").map(PathBuf::from).as_deref(),
+            )?,
+            git: ui_config.git,
+        };
 
         let mut app = App {
             running: true,
-            ui_config: UIConfig {
-                colored: false,
-                confirming_deletion: false,
-                sort_by: SortBy::Title,
-                move_to_trash: true,
-                open_file: true,
-                debug_enabled: false,
-            },
-            task_manager: TaskManager::<S>::default(),
+            ui_config,
+            task_manager: TaskManager::<S>::new(scan_options),
+            delete_manager: DeleteManager::default(),
+            delete_failures: vec![],
+            dry_run_deletions: vec![],
+            deletion_history: vec![],
+            trashed_batch: vec![],
+            audit_log: config.audit_log.clone().map(AuditLog::new),
+            #[cfg(feature = "watch")]
+            watcher: None,
+            cache_dir: None,
+            cache_validator: None,
+            scan_root: current_path.clone(),
+            largest_cursor: 0,
+            errors_cursor: 0,
             store: S::new(),
             logger: Logger::default(),
             fps_counter: FPSCounter::default(),
@@ -72,29 +136,449 @@ impl<S: DataStore<DataStoreKey>> App<S> {
         };
 
         app.store.set_current_path(&current_path);
+        app.delete_manager.dry_run = app.ui_config.dry_run;
+        app.delete_manager.size_mode = app.ui_config.size_mode.clone();
+        // Dumps and snapshots are never cached
+        if config.cache.unwrap_or(false) && !app.ui_config.read_only {
+            app.cache_dir = config.cache_dir.clone();
+        }
 
-        app
+        Ok(app)
     }
 
     pub fn init(&mut self) {
         let path_buf = self.store.get_current_path().clone();
         self.logger.log(path_buf.to_string_lossy().to_string());
+        self.scan_root = path_buf.clone();
+
+        self.start_scan(vec![path_buf]);
+    }
+
+    fn start_scan(&mut self, paths: Vec<PathBuf>) {
+        self.logger.start_timer("Traversal");
+        if let Err(err) = self.task_manager.start(paths) {
+            self.logger.stop_timer("Traversal");
+            self.logger
+                .error(format!("Failed to start traversal: {err}"));
+        }
+    }
+
+    /// Fills store with the cached tree of current path and starts its revalidation.
+    /// Returns false when caching is disabled or there is no usable cache
+    pub fn load_cache(&mut self) -> bool {
+        let Some(cache_dir) = &self.cache_dir else {
+            return false;
+        };
+        let root = self.store.get_current_path().clone();
+        let cache = match ScanCache::read(&cache_file_path(cache_dir, &root)) {
+            Ok(cache) => cache,
+            Err(err) if err.kind() == ErrorKind::NotFound => return false,
+            Err(err) => {
+                self.logger
+                    .error(format!("Failed to read scan cache: {err}"));
+                return false;
+            }
+        };
+        if cache.key != CacheKey::new(&root, &self.task_manager.options) {
+            self.logger
+                .log("Scan cache was made with other options, scanning".into());
+            return false;
+        }
+
+        cache.to_store(&mut self.store, &self.task_manager.options.protect);
+        self.task_manager.largest_files.collect(&mut self.store);
+        self.cache_validator = Some(CacheValidator::start(cache.folder_states()));
+        self.scan_root = root;
+        self.logger
+            .log(format!("Loaded scan cache from {}", cache.timestamp));
+        true
+    }
 
-        self.task_manager.start(vec![path_buf], &mut self.logger);
+    /// Writes scanned tree to the cache. Unfinished scans and dry-run sessions are not saved
+    pub fn save_cache(&self) -> io::Result<()> {
+        let Some(cache_dir) = &self.cache_dir else {
+            return Ok(());
+        };
+        if self.ui_config.dry_run || !self.task_manager.is_done() || !self.delete_manager.is_done()
+        {
+            return Ok(());
+        }
+        let key = CacheKey::new(&self.scan_root, &self.task_manager.options);
+        ScanCache::from_store(&self.store, key).write(&cache_file_path(cache_dir, &self.scan_root))
+    }
+
+    /// Modified folders are synced once scans and deletions are finished, they update the same
+    /// entries
+    fn process_cache_validation(&mut self) {
+        let Some(validator) = self.cache_validator.as_ref() else {
+            return;
+        };
+        if !self.task_manager.is_done() || !self.delete_manager.is_done() {
+            return;
+        }
+        for event in validator.take_events() {
+            match event {
+                ValidationEvent::Unchanged(path) => self.mark_revalidated(&path),
+                ValidationEvent::Changed {
+                    path,
+                    modified,
+                    entries,
+                } => {
+                    self.sync_paths(entries);
+                    if let Some(folder) = self.store.get_folder_mut(&path) {
+                        folder.modified = modified;
+                    }
+                    self.mark_revalidated(&path);
+                }
+                ValidationEvent::Finished => {
+                    self.logger.log("Scan cache is revalidated".into());
+                    self.cache_validator = None;
+                }
+            }
+        }
+    }
+
+    /// Clears stale marks of files inside folder and of the folder entry itself
+    fn mark_revalidated(&mut self, path: &Path) {
+        if let Some(folder) = self.store.get_folder_mut(&path.to_path_buf()) {
+            for entry in folder.entries.iter_mut() {
+                if entry.kind == FolderEntryType::File {
+                    entry.is_stale = false;
+                }
+            }
+        }
+        let (Some(parent), Some(title)) = (path.parent(), path.file_name()) else {
+            return;
+        };
+        let title = title.to_string_lossy();
+        if let Some(entry) = self
+            .store
+            .get_folder_mut(&parent.to_path_buf())
+            .and_then(|folder| {
+                folder
+                    .entries
+                    .iter_mut()
+                    .find(|e| e.kind == FolderEntryType::Folder && *e.title == *title)
+            })
+        {
+            entry.is_stale = false;
+        }
+    }
+
+    /// Opens ncdu dump as a read-only session instead of scanning
+    pub fn import(&mut self, path: &Path) -> io::Result<()> {
+        let file = File::open(path)?;
+        let root = read_ncdu(
+            BufReader::new(file),
+            &self.ui_config.size_mode,
+            &mut self.store,
+        )?;
+        self.store.set_current_path(&root);
+        self.task_manager.largest_files.collect(&mut self.store);
+        self.ui_config.read_only = true;
+        // Files of the dump may not exist on this machine
+        self.ui_config.open_file = false;
+        self.logger
+            .log(format!("Imported {}", path.to_string_lossy()));
+        Ok(())
+    }
+
+    /// Opens comparison of two snapshots as a read-only session
+    pub fn open_diff(&mut self, old_path: &Path, new_path: &Path) -> io::Result<()> {
+        let old = Snapshot::read(old_path)?;
+        let new = Snapshot::read(new_path)?;
+        let root = diff_snapshots(&old, &new, &mut self.store)?;
+        self.store.set_current_path(&root);
+        self.task_manager.largest_files.collect(&mut self.store);
+        self.ui_config.size_mode = new.size_mode;
+        self.ui_config.read_only = true;
+        self.ui_config.diff = true;
+        self.ui_config.open_file = false;
+        self.logger.log(format!(
+            "Compared {} with {}",
+            old_path.to_string_lossy(),
+            new_path.to_string_lossy()
+        ));
+        Ok(())
+    }
+
+    /// Logs refusal of action which would touch the filesystem of a read-only session
+    fn refuse_if_read_only(&mut self, action: &str) -> bool {
+        if self.ui_config.read_only {
+            self.logger
+                .error(format!("Session is read-only, {action} is disabled"));
+        }
+        self.ui_config.read_only
     }
 
     pub fn reset(&mut self) {
+        if self.refuse_if_read_only("rescanning") {
+            return;
+        }
         let current_path = self.store.get_current_path().clone();
         self.store = S::new();
         self.store.set_current_path(&current_path);
+        self.task_manager.reset();
+        self.cache_validator = None;
 
         self.init();
     }
 
+    /// Walks current folder again, records of other folders and their cursors are kept
+    pub fn on_refresh_current(&mut self) {
+        if self.refuse_if_read_only("rescanning") {
+            return;
+        }
+        if !self.task_manager.is_done() || !self.delete_manager.is_done() {
+            self.logger
+                .error("Refresh is disabled until scan and deletion are finished".into());
+            return;
+        }
+        let path = self.store.get_current_path().clone();
+        let (Some(folder_path), Some(title)) = (path.parent(), path.file_name()) else {
+            return self.reset();
+        };
+        let folder_path = folder_path.to_path_buf();
+        let title = title.to_string_lossy();
+        let entry = self.store.get_folder(&folder_path).and_then(|folder| {
+            folder
+                .entries
+                .iter()
+                .find(|e| e.kind == FolderEntryType::Folder && *e.title == *title)
+                .cloned()
+        });
+        let Some(entry) = entry else {
+            // Nothing is known above the scanned root
+            return self.reset();
+        };
+
+        self.logger
+            .log(format!("Refreshing {}", path.to_string_lossy()));
+        if let Some(hard_links) = self.store.get_folder(&path).map(|folder| folder.hard_links) {
+            let mut ancestor_path = folder_path.clone();
+            while let Some(ancestor) = self.store.get_folder_mut(&ancestor_path) {
+                ancestor.hard_links = ancestor.hard_links.saturating_sub(hard_links);
+                match ancestor_path.parent() {
+                    Some(parent) => ancestor_path = parent.to_path_buf(),
+                    None => break,
+                }
+            }
+        }
+        // Entry is added again with its new size when the walk reaches it
+        self.remove_deleted_entry(&DeleteTask { folder_path, entry });
+        self.start_scan(vec![path]);
+    }
+
     /// Handles the tick event of the terminal.
     pub fn tick(&mut self) {
+        let was_scanning = !self.task_manager.is_done();
+        self.task_manager.process_results(&mut self.store);
+        if was_scanning && self.task_manager.is_done() {
+            self.logger.stop_timer("Traversal");
+            self.hide_dry_run_deletions();
+        }
+        self.process_deletions();
+        self.process_cache_validation();
+        #[cfg(feature = "watch")]
+        self.process_fs_changes();
+    }
+
+    /// Starts watching current path, store is kept in sync with its changes
+    #[cfg(feature = "watch")]
+    pub fn watch(&mut self) -> notify::Result<()> {
+        let root = self.store.get_current_path().clone();
+        self.watcher = Some(FsWatcher::new(&root)?);
+        self.logger
+            .log(format!("Watching {}", root.to_string_lossy()));
+        Ok(())
+    }
+
+    /// Changes are applied once scans and deletions are finished, they update the same entries
+    #[cfg(feature = "watch")]
+    fn process_fs_changes(&mut self) {
+        let Some(watcher) = self.watcher.as_mut() else {
+            return;
+        };
+        for err in watcher.collect() {
+            self.logger.error(format!("Watcher error: {err}"));
+        }
+        if self.task_manager.is_done() && self.delete_manager.is_done() {
+            let changes = watcher.take_changes();
+            self.sync_paths(changes);
+        }
+    }
+
+    /// Reads provided paths again and updates their entries and sizes of every ancestor.
+    /// Paths outside of scanned folders are skipped
+    pub fn sync_paths(&mut self, paths: Vec<PathBuf>) {
+        for path in paths {
+            let (Some(folder_path), Some(title)) = (path.parent(), path.file_name()) else {
+                continue;
+            };
+            let folder_path = folder_path.to_path_buf();
+            let title = title.to_string_lossy().to_string();
+            let Some(folder) = self.store.get_folder(&folder_path) else {
+                continue;
+            };
+            if self.is_inside_excluded(&folder_path) {
+                continue;
+            }
+            let mut stored_entry = folder
+                .entries
+                .iter()
+                .find(|e| e.kind != FolderEntryType::Parent && e.title == title)
+                .cloned();
+            let metadata = path.symlink_metadata();
+
+            // Entry replaced with entry of another kind is added again
+            if let (Some(entry), Ok(metadata)) = (&stored_entry, &metadata) {
+                if (entry.kind == FolderEntryType::Folder) != metadata.is_dir() {
+                    self.remove_deleted_entry(&DeleteTask {
+                        folder_path: folder_path.clone(),
+                        entry: entry.clone(),
+                    });
+                    stored_entry = None;
+                }
+            }
+
+            match (stored_entry, metadata) {
+                (Some(entry), Err(_)) => {
+                    self.remove_deleted_entry(&DeleteTask { folder_path, entry })
+                }
+                (None, Err(_)) => {}
+                // Folder content is updated by events of its own entries
+                (Some(_), Ok(metadata)) if metadata.is_dir() => {}
+                (None, Ok(metadata)) if metadata.is_dir() => {
+                    if self.task_manager.options.exclude.is_excluded(&path) {
+                        self.add_entry(
+                            &path,
+                            FolderEntry {
+                                title,
+                                kind: FolderEntryType::Folder,
+                                is_loaded: true,
+                                is_excluded: true,
+                                git_status: self.git_status_of(&path, true),
+                                ..FolderEntry::default()
+                            },
+                        );
+                    } else {
+                        // Folder entry is added and its size is propagated while scanning
+                        self.logger
+                            .log(format!("Scanning new folder {}", path.to_string_lossy()));
+                        self.start_scan(vec![path]);
+                    }
+                }
+                (stored_entry, Ok(metadata)) => {
+                    if self.task_manager.options.exclude.is_excluded(&path) {
+                        continue;
+                    }
+                    // Hard link to a file which is counted at another path has no size
+                    let link_id = hard_link_id(&metadata);
+                    let is_duplicate_link = link_id.is_some_and(|id| {
+                        self.task_manager
+                            .seen_hard_links
+                            .get(&id)
+                            .is_some_and(|counted_at| *counted_at != path)
+                    });
+                    let size = match is_duplicate_link {
+                        true => 0,
+                        false => self.task_manager.options.size_mode.size_of(&metadata),
+                    };
+                    let is_unchanged = stored_entry
+                        .as_ref()
+                        .is_some_and(|entry| entry.size == Some(size));
+                    if let (Some(entry), false) = (&stored_entry, is_unchanged) {
+                        self.remove_deleted_entry(&DeleteTask {
+                            folder_path: folder_path.clone(),
+                            entry: entry.clone(),
+                        });
+                    }
+                    // File gets linked while watching, so it may be not counted as a link yet
+                    match (link_id, is_duplicate_link) {
+                        (Some(id), false) => {
+                            self.task_manager.seen_hard_links.insert(id, path.clone());
+                        }
+                        (Some(_), true) if !is_unchanged => self.count_duplicate_link(&folder_path),
+                        _ => {}
+                    }
+                    if is_unchanged {
+                        continue;
+                    }
+                    let git_status = match &stored_entry {
+                        Some(entry) => entry.git_status,
+                        None => self.git_status_of(&path, false),
+                    };
+                    let stored_entry = stored_entry.unwrap_or_default();
+                    let entry = FolderEntry {
+                        title,
+                        size: Some(size),
+                        kind: FolderEntryType::File,
+                        is_loaded: true,
+                        git_status,
+                        ignored_size: match git_status {
+                            Some(GitStatus::Ignored) => size,
+                            _ => 0,
+                        },
+                        is_protected: self.task_manager.options.protect.is_protected(&path),
+                        ..stored_entry
+                    };
+                    self.add_entry(&path, entry);
+                }
+            }
+        }
+    }
+
+    /// Classifies entry which was not scanned, `None` outside of git mode
+    fn git_status_of(&self, path: &Path, is_dir: bool) -> Option<GitStatus> {
+        let folder_path = path.parent()?;
         self.task_manager
-            .process_results(&mut self.store, &mut self.logger);
+            .options
+            .git
+            .then(|| GitFilter::for_path(folder_path).enter(folder_path))?
+            .status(path, is_dir)
+    }
+
+    /// Duplicate link is reported by its folder and every ancestor, like the walker does
+    fn count_duplicate_link(&mut self, folder_path: &Path) {
+        for ancestor in folder_path.ancestors() {
+            let Some(folder) = self.store.get_folder_mut(&ancestor.to_path_buf()) else {
+                break;
+            };
+            folder.hard_links += 1;
+        }
+    }
+
+    /// Content of excluded folders is not traversed and not updated
+    fn is_inside_excluded(&self, folder_path: &Path) -> bool {
+        let (Some(parent), Some(title)) = (folder_path.parent(), folder_path.file_name()) else {
+            return false;
+        };
+        self.store
+            .get_folder(&parent.to_path_buf())
+            .and_then(|parent| {
+                parent
+                    .entries
+                    .iter()
+                    .find(|e| e.kind == FolderEntryType::Folder && *e.title == *title)
+            })
+            .is_some_and(|entry| entry.is_excluded)
+    }
+
+    /// Puts entry to its folder and adds its size to every ancestor
+    fn add_entry(&mut self, path: &Path, entry: FolderEntry) {
+        let Some(folder_path) = path.parent() else {
+            return;
+        };
+        if let (FolderEntryType::File, Some(size)) = (&entry.kind, entry.size) {
+            self.task_manager
+                .largest_files
+                .push(path.to_path_buf(), size);
+        }
+        self.propagate_size_update_upwards(path, &entry, DiffKind::Add);
+        if let Some(folder) = self.store.get_folder_mut(&folder_path.to_path_buf()) {
+            folder.entries.push(entry);
+            folder.sorted_by = None;
+        }
     }
 
     /// Set running to false to quit the application.
@@ -103,7 +587,68 @@ impl<S: DataStore<DataStoreKey>> App<S> {
     }
 
     pub fn on_escape(&mut self) {
+        if !self.delete_manager.is_done() {
+            self.delete_manager.cancel();
+        }
         self.ui_config.confirming_deletion = false;
+        self.ui_config.show_errors = false;
+        self.ui_config.show_largest = false;
+        self.delete_failures.clear();
+    }
+
+    /// Error list starts from the first error
+    pub fn on_toggle_errors(&mut self) {
+        self.ui_config.show_errors = !self.ui_config.show_errors;
+        self.ui_config.show_largest = false;
+        self.errors_cursor = 0;
+    }
+
+    /// Marks and sorting belong to the folder hidden behind the largest files view
+    fn refuse_if_largest_shown(&mut self, action: &str) -> bool {
+        if self.ui_config.show_largest {
+            self.logger
+                .error(format!("{action} is disabled in largest files view"));
+        }
+        self.ui_config.show_largest
+    }
+
+    /// Largest files view starts from the largest one
+    pub fn on_toggle_largest(&mut self) {
+        self.ui_config.show_largest = !self.ui_config.show_largest;
+        self.ui_config.show_errors = false;
+        self.ui_config.confirming_deletion = false;
+        self.largest_cursor = 0;
+    }
+
+    /// File under cursor of the largest files view, cursor is kept inside the list
+    pub fn selected_largest_file(&mut self) -> Option<(PathBuf, u64)> {
+        let files = self.task_manager.largest_files.to_sorted_vec();
+        self.largest_cursor = self.largest_cursor.min(files.len().saturating_sub(1));
+        files.into_iter().nth(self.largest_cursor)
+    }
+
+    /// Opens folder containing the selected largest file, with cursor on that file
+    fn jump_to_largest_file(&mut self) {
+        let Some((path, _)) = self.selected_largest_file() else {
+            return;
+        };
+        let (Some(folder_path), Some(title)) = (path.parent(), path.file_name()) else {
+            return;
+        };
+        let folder_path = folder_path.to_path_buf();
+        if !self.store.has_path(&folder_path) {
+            return;
+        }
+        self.store.set_current_path(&folder_path);
+        self.sort_current_folder();
+        let title = title.to_string_lossy();
+        if let Some(folder) = self.store.get_current_folder_mut() {
+            if let Some(index) = folder.entries.iter().position(|e| *e.title == *title) {
+                folder.cursor_index = index;
+            }
+        }
+        self.logger.log(folder_path.to_string_lossy().to_string());
+        self.ui_config.show_largest = false;
     }
 
     pub fn on_toggle_coloring(&mut self) {
@@ -111,11 +656,17 @@ impl<S: DataStore<DataStoreKey>> App<S> {
     }
 
     pub fn on_toggle_sorting(&mut self) {
+        if self.refuse_if_largest_shown("Sorting") {
+            return;
+        }
         match self.ui_config.sort_by {
             SortBy::Title => {
                 self.ui_config.sort_by = SortBy::Size;
             }
-            SortBy::Size => {
+            SortBy::Size if self.ui_config.diff => {
+                self.ui_config.sort_by = SortBy::Delta;
+            }
+            SortBy::Size | SortBy::Delta => {
                 self.ui_config.sort_by = SortBy::Title;
             }
         }
@@ -130,8 +681,26 @@ impl<S: DataStore<DataStoreKey>> App<S> {
         self.ui_config.move_to_trash = !self.ui_config.move_to_trash;
     }
 
+    /// Switches between apparent and allocated sizes, sizes are collected again
+    pub fn on_toggle_size_mode(&mut self) {
+        if self.refuse_if_read_only("switching size mode") {
+            return;
+        }
+        self.ui_config.size_mode = match self.ui_config.size_mode {
+            SizeMode::Apparent => SizeMode::Allocated,
+            SizeMode::Allocated => SizeMode::Apparent,
+        };
+        self.task_manager.options.size_mode = self.ui_config.size_mode.clone();
+        self.delete_manager.size_mode = self.ui_config.size_mode.clone();
+        self.reset();
+    }
+
     pub fn on_cursor_up(&mut self) {
-        if let Some(folder) = self.store.get_current_folder_mut() {
+        if self.ui_config.show_errors {
+            self.errors_cursor = self.errors_cursor.saturating_sub(1);
+        } else if self.ui_config.show_largest {
+            self.largest_cursor = self.largest_cursor.saturating_sub(1);
+        } else if let Some(folder) = self.store.get_current_folder_mut() {
             if folder.cursor_index > 0 {
                 folder.cursor_index -= 1;
             }
@@ -140,7 +709,13 @@ impl<S: DataStore<DataStoreKey>> App<S> {
     }
 
     pub fn on_cursor_down(&mut self) {
-        if let Some(folder) = self.store.get_current_folder_mut() {
+        if self.ui_config.show_errors {
+            let last = self.store.get_errors().len().saturating_sub(1);
+            self.errors_cursor = (self.errors_cursor + 1).min(last);
+        } else if self.ui_config.show_largest {
+            let last = self.task_manager.largest_files.len().saturating_sub(1);
+            self.largest_cursor = (self.largest_cursor + 1).min(last);
+        } else if let Some(folder) = self.store.get_current_folder_mut() {
             if folder.cursor_index < folder.entries.len() - 1 {
                 folder.cursor_index += 1;
             }
@@ -156,21 +731,64 @@ impl<S: DataStore<DataStoreKey>> App<S> {
     }
 
     fn navigate_to_parent(&mut self) {
+        let parent = self
+            .store
+            .get_current_path()
+            .parent()
+            .map(Path::to_path_buf);
+        if !parent.is_some_and(|parent| self.store.has_path(&parent))
+            && self.refuse_if_read_only("leaving the dump root")
+        {
+            return;
+        }
         self.store.move_to_parent();
 
         let updated_path = self.store.get_current_path().to_path_buf();
         self.logger.log(updated_path.to_string_lossy().to_string());
 
-        let to_process = self
+        let mut to_process = self
             .task_manager
             .process_path_sync(&mut self.store, &updated_path);
+        to_process.retain(|child_path| {
+            !self
+                .dry_run_deletions
+                .iter()
+                .any(|task| task.path() == *child_path)
+        });
+        self.hide_dry_run_deletions();
 
         self.sort_current_folder();
 
-        self.task_manager.start(to_process, &mut self.logger);
+        self.start_scan(to_process);
+    }
+
+    /// Entries removed in dry-run mode still exist on disk and are read again with their folder
+    fn hide_dry_run_deletions(&mut self) {
+        let reread: Vec<DeleteTask> = self
+            .dry_run_deletions
+            .iter()
+            .filter_map(|task| {
+                let folder = self.store.get_folder(&task.folder_path)?;
+                let entry = folder
+                    .entries
+                    .iter()
+                    .find(|e| e.title == task.entry.title)?;
+                Some(DeleteTask {
+                    folder_path: task.folder_path.clone(),
+                    entry: entry.clone(),
+                })
+            })
+            .collect();
+        for task in reread {
+            self.remove_deleted_entry(&task);
+        }
     }
 
     fn navigate_to_child(&mut self, title: &str) {
+        let child = self.store.get_current_path().join(title);
+        if !self.store.has_path(&child) && self.refuse_if_read_only("scanning") {
+            return;
+        }
         self.store.move_to_child(title);
 
         self.logger
@@ -179,20 +797,25 @@ impl<S: DataStore<DataStoreKey>> App<S> {
         match self.store.get_current_folder() {
             Some(_) => {}
             None => {
-                self.task_manager.start(
-                    vec![self.store.get_current_path().clone()],
-                    &mut self.logger,
-                );
+                let path = self.store.get_current_path().clone();
+                self.start_scan(vec![path]);
             }
         }
     }
 
+    /// Closes the largest files view instead of leaving the folder hidden behind it
     pub fn on_backspace(&mut self) {
+        if self.ui_config.show_largest {
+            self.ui_config.show_largest = false;
+            return;
+        }
         self.navigate_to_parent();
     }
 
     pub fn on_enter(&mut self) {
-        if let Some(folder) = self.store.get_current_folder().cloned() {
+        if self.ui_config.show_largest {
+            self.jump_to_largest_file();
+        } else if let Some(folder) = self.store.get_current_folder().cloned() {
             let entry = folder.get_selected_entry();
 
             match entry.kind {
@@ -200,7 +823,9 @@ impl<S: DataStore<DataStoreKey>> App<S> {
                     self.navigate_to_parent();
                 }
                 FolderEntryType::Folder => {
-                    self.navigate_to_child(&entry.title);
+                    if !entry.is_excluded {
+                        self.navigate_to_child(&entry.title);
+                    }
                 }
                 FolderEntryType::File => {
                     if self.ui_config.open_file {
@@ -214,80 +839,422 @@ impl<S: DataStore<DataStoreKey>> App<S> {
         self.ui_config.confirming_deletion = false;
     }
 
+    pub fn on_toggle_mark(&mut self) {
+        if self.refuse_if_largest_shown("Marking") {
+            return;
+        }
+        if let Some(folder) = self.store.get_current_folder_mut() {
+            folder.toggle_selected_mark();
+        }
+        self.ui_config.confirming_deletion = false;
+        self.on_cursor_down();
+    }
+
+    pub fn on_mark_all(&mut self) {
+        if self.refuse_if_largest_shown("Marking") {
+            return;
+        }
+        if let Some(folder) = self.store.get_current_folder_mut() {
+            folder.mark_all();
+        }
+        self.ui_config.confirming_deletion = false;
+    }
+
+    pub fn on_invert_marks(&mut self) {
+        if self.refuse_if_largest_shown("Marking") {
+            return;
+        }
+        if let Some(folder) = self.store.get_current_folder_mut() {
+            folder.invert_marks();
+        }
+        self.ui_config.confirming_deletion = false;
+    }
+
+    /// Deletes marked entries of current folder or the one under cursor if none are marked
     pub fn on_delete(&mut self) {
-        if let Some(mut folder) = self.store.get_current_folder().cloned() {
-            let entry = folder.get_selected_entry();
+        if self.refuse_if_read_only("deletion") {
+            return;
+        }
+        if !self.delete_manager.is_done() {
+            self.logger.log("Deletion is in progress".into());
+            return;
+        }
+        let Some((folder_path, to_delete)) = self.deletion_targets() else {
+            return;
+        };
 
-            let mut to_delete_path = PathBuf::from(&self.store.get_current_path());
-            to_delete_path.push(&entry.title);
+        if to_delete.is_empty() {
+            return;
+        }
+
+        // Batch with any protected entry is refused as a whole, without confirmation
+        let protected: Vec<DeleteFailure> = to_delete
+            .iter()
+            .filter(|entry| {
+                entry.is_protected
+                    || self
+                        .task_manager
+                        .options
+                        .protect
+                        .is_protected(&folder_path.join(&entry.title))
+            })
+            .map(|entry| DeleteFailure {
+                task: DeleteTask {
+                    folder_path: folder_path.clone(),
+                    entry: entry.clone(),
+                },
+                kind: io::ErrorKind::PermissionDenied,
+                message: "protected path, refusing to delete".into(),
+                in_trash: false,
+            })
+            .collect();
+        if !protected.is_empty() {
+            for failure in protected.iter() {
+                self.logger.error(format!(
+                    "Refused to delete protected path {}",
+                    failure.task.path().to_string_lossy()
+                ));
+            }
+            self.delete_failures = protected;
+            self.ui_config.confirming_deletion = false;
+            return;
+        }
 
+        if !self.ui_config.confirming_deletion || self.ui_config.confirming_permanent {
+            self.ui_config.confirming_deletion = true;
+            self.ui_config.confirming_permanent = false;
+            return;
+        }
+
+        let tasks = to_delete
+            .into_iter()
+            .map(|entry| DeleteTask {
+                folder_path: folder_path.clone(),
+                entry,
+            })
+            .collect();
+        self.delete_failures.clear();
+        self.delete_manager
+            .start(tasks, self.ui_config.move_to_trash);
+        self.ui_config.confirming_deletion = false;
+    }
+
+    /// Folder and its entries to delete: the selected largest file when its view is shown,
+    /// otherwise marked entries of current folder or the one under cursor
+    fn deletion_targets(&mut self) -> Option<(PathBuf, Vec<FolderEntry>)> {
+        if self.ui_config.show_largest {
+            let (path, _) = self.selected_largest_file()?;
+            let folder_path = path.parent()?.to_path_buf();
+            let title = path.file_name()?.to_string_lossy();
+            let entry = self
+                .store
+                .get_folder(&folder_path)?
+                .entries
+                .iter()
+                .find(|e| e.kind == FolderEntryType::File && *e.title == *title)?
+                .clone();
+            return Some((folder_path, vec![entry]));
+        }
+
+        let folder = self.store.get_current_folder()?;
+        let marked = folder.get_marked_entries();
+        let to_delete: Vec<FolderEntry> = if marked.is_empty() {
+            let entry = folder.get_selected_entry();
             match entry.kind {
-                FolderEntryType::Parent => {}
-                FolderEntryType::Folder => {
-                    if !self.ui_config.confirming_deletion {
-                        self.ui_config.confirming_deletion = true;
-                    } else if delete_folder(&to_delete_path, &self.ui_config).is_ok() {
-                        if let Some(subfolder_size) = entry.size {
-                            self.propagate_size_update_upwards(
-                                &to_delete_path,
-                                subfolder_size,
-                                DiffKind::Subtract,
-                            );
-                        }
-                        folder.remove_selected();
-                        self.store.remove_path(&to_delete_path);
-                        self.store.set_current_folder(folder.clone());
-                        self.ui_config.confirming_deletion = false;
-                    }
+                FolderEntryType::Parent => vec![],
+                _ => vec![entry.clone()],
+            }
+        } else {
+            marked.into_iter().cloned().collect()
+        };
+        Some((self.store.get_current_path().clone(), to_delete))
+    }
+
+    /// Applies results of background deletion to the store
+    fn process_deletions(&mut self) {
+        let in_trash = self.delete_manager.move_to_trash;
+        for (task, outcome) in self.delete_manager.process_results() {
+            let path = task.path();
+            if !self.delete_manager.dry_run {
+                self.record_deletion(&task, &outcome, in_trash);
+            }
+            if let DeleteOutcome::Failed(err) = &outcome {
+                self.logger.error(format!(
+                    "Failed to delete {}: {err}",
+                    path.to_string_lossy()
+                ));
+                self.delete_failures.push(DeleteFailure {
+                    task: task.clone(),
+                    kind: err.kind(),
+                    message: err.to_string(),
+                    in_trash,
+                });
+            }
+            match outcome {
+                DeleteOutcome::Deleted if self.delete_manager.dry_run => {
+                    self.remove_deleted_entry(&task);
+                    self.dry_run_deletions
+                        .retain(|deleted| deleted.path() != path);
+                    self.dry_run_deletions.push(task);
                 }
-                FolderEntryType::File => {
-                    if !self.ui_config.confirming_deletion {
-                        self.ui_config.confirming_deletion = true;
-                    } else if delete_file(&to_delete_path, &self.ui_config).is_ok() {
-                        if let Some(subfile_size) = entry.size {
-                            let parent_folder = PathBuf::from(to_delete_path.parent().unwrap());
-                            self.propagate_size_update_upwards(
-                                &parent_folder,
-                                subfile_size,
-                                DiffKind::Subtract,
-                            );
-                        }
-                        folder.remove_selected();
-                        self.store.set_current_folder(folder.clone());
-                        self.ui_config.confirming_deletion = false;
-                    }
+                DeleteOutcome::Deleted if in_trash => {
+                    self.remove_deleted_entry(&task);
+                    self.trashed_batch.push(task);
+                }
+                DeleteOutcome::Deleted => self.remove_deleted_entry(&task),
+                DeleteOutcome::Cancelled | DeleteOutcome::Failed(_) if !path.exists() => {
+                    self.remove_deleted_entry(&task)
+                }
+                DeleteOutcome::Cancelled | DeleteOutcome::Failed(_)
+                    if task.entry.kind == FolderEntryType::Folder && !in_trash =>
+                {
+                    // Folder may be partially removed, its remaining content is collected again
+                    self.logger.log(format!(
+                        "Deletion stopped, rescanning {}",
+                        path.to_string_lossy()
+                    ));
+                    self.remove_deleted_entry(&task);
+                    self.start_scan(vec![path]);
+                }
+                DeleteOutcome::Cancelled | DeleteOutcome::Failed(_) => {}
+            }
+        }
+
+        if self.delete_manager.is_done() && !self.trashed_batch.is_empty() {
+            let batch = std::mem::take(&mut self.trashed_batch);
+            self.deletion_history.push(batch);
+        }
+    }
+
+    /// Appends finished deletion to the audit log
+    fn record_deletion(&mut self, task: &DeleteTask, outcome: &DeleteOutcome, in_trash: bool) {
+        let Some(audit_log) = &self.audit_log else {
+            return;
+        };
+        let (status, error) = match outcome {
+            DeleteOutcome::Deleted => (AuditStatus::Deleted, None),
+            DeleteOutcome::Cancelled => (AuditStatus::Cancelled, None),
+            DeleteOutcome::Failed(err) => (AuditStatus::Failed, Some(err.to_string())),
+        };
+        let record = AuditRecord {
+            timestamp: format_timestamp(SystemTime::now()),
+            path: task.path(),
+            size: task.entry.size,
+            kind: match task.entry.kind {
+                FolderEntryType::Folder => AuditEntryKind::Folder,
+                _ => AuditEntryKind::File,
+            },
+            method: match in_trash {
+                true => AuditMethod::Trash,
+                false => AuditMethod::Permanent,
+            },
+            status,
+            error,
+        };
+        if let Err(err) = audit_log.append(&record) {
+            self.logger.error(format!(
+                "Failed to write audit log {}: {err}",
+                audit_log.path.to_string_lossy()
+            ));
+        }
+    }
+
+    /// Deletes entries which could not be moved to trash permanently
+    pub fn on_retry_permanently(&mut self) {
+        if !self.delete_manager.is_done() || !self.delete_failures.iter().any(|f| f.in_trash) {
+            return;
+        }
+        // Paths and their total size are shown until the second press
+        if !self.ui_config.confirming_deletion || !self.ui_config.confirming_permanent {
+            self.ui_config.confirming_deletion = true;
+            self.ui_config.confirming_permanent = true;
+            return;
+        }
+        self.ui_config.confirming_deletion = false;
+        self.ui_config.confirming_permanent = false;
+
+        let (retry, rest): (Vec<DeleteFailure>, Vec<DeleteFailure>) = self
+            .delete_failures
+            .drain(..)
+            .partition(|failure| failure.in_trash);
+        self.delete_failures = rest;
+
+        // Entries are taken from the store, their sizes may be updated since the failure
+        let tasks: Vec<DeleteTask> = retry
+            .into_iter()
+            .filter_map(|failure| {
+                let folder = self.store.get_folder_mut(&failure.task.folder_path)?;
+                let entry = folder.entries.iter().find(|e| {
+                    e.kind != FolderEntryType::Parent && e.title == failure.task.entry.title
+                })?;
+                Some(DeleteTask {
+                    folder_path: failure.task.folder_path,
+                    entry: entry.clone(),
+                })
+            })
+            .collect();
+        if !tasks.is_empty() {
+            self.delete_manager.start(tasks, false);
+        }
+    }
+
+    /// Restores the last batch of entries moved to trash
+    pub fn on_undo(&mut self) {
+        if !self.delete_manager.is_done() {
+            return;
+        }
+        let Some(batch) = self.deletion_history.pop() else {
+            self.logger.log("Nothing to undo".into());
+            return;
+        };
+
+        let paths: Vec<PathBuf> = batch.iter().map(DeleteTask::path).collect();
+        let results = restore_from_trash(&paths);
+        let mut failed = vec![];
+        for (task, result) in batch.into_iter().zip(results) {
+            let path = task.path();
+            match result {
+                Ok(_) => {
+                    self.logger
+                        .log(format!("Restored {}", path.to_string_lossy()));
+                    self.restore_entry(&task);
+                }
+                Err(err) => {
+                    self.logger.error(format!(
+                        "Failed to restore {}: {err}",
+                        path.to_string_lossy()
+                    ));
+                    failed.push(task);
                 }
             }
         }
+        // Entries which were not restored can be retried
+        if !failed.is_empty() {
+            self.deletion_history.push(failed);
+        }
     }
 
-    /// Currently updates size after deletion
+    /// Puts restored entry back to its folder and adds its size to every ancestor
+    fn restore_entry(&mut self, task: &DeleteTask) {
+        let path = task.path();
+        if !self.store.has_path(&task.folder_path) {
+            return;
+        }
+        match task.entry.kind {
+            // Folder content is collected again, its size is added to ancestors while scanning
+            FolderEntryType::Folder => self.start_scan(vec![path]),
+            _ => {
+                if let Some(folder) = self.store.get_folder_mut(&task.folder_path) {
+                    folder.entries.push(FolderEntry {
+                        is_marked: false,
+                        ..task.entry.clone()
+                    });
+                    folder.sorted_by = None;
+                }
+                if let Some(size) = task.entry.size {
+                    self.task_manager.largest_files.push(path.clone(), size);
+                }
+                self.propagate_size_update_upwards(&path, &task.entry, DiffKind::Add);
+            }
+        }
+    }
+
+    /// Removes deleted entry from its folder and every record inside it
+    fn remove_deleted_entry(&mut self, task: &DeleteTask) {
+        let path = task.path();
+        self.task_manager.largest_files.remove(&path);
+        self.task_manager.forget_hard_links(&path);
+        self.propagate_size_update_upwards(&path, &task.entry, DiffKind::Subtract);
+        if let Some(folder) = self.store.get_folder_mut(&task.folder_path) {
+            folder.remove_entry(&task.entry.title);
+            folder.sorted_by = None;
+        }
+        if task.entry.kind == FolderEntryType::Folder {
+            self.store.remove_path(&path);
+        }
+    }
+
+    /// Applies size change of provided entry to every folder record containing it
     fn propagate_size_update_upwards(
         &mut self,
-        to_delete_path: &Path,
-        entry_diff: u64,
+        entry_path: &Path,
+        entry: &FolderEntry,
         diff_kind: DiffKind,
     ) {
-        let mut parent_path = to_delete_path.to_path_buf();
-        while let Some(parent) = parent_path.parent() {
-            if let Some(parent_folder) = self.store.get_folder_mut(&parent_path) {
-                if let Some(parent_folder_entry) =
-                    parent_folder.entries.get_mut(parent_folder.cursor_index)
-                {
-                    if let Some(size) = parent_folder_entry.size.as_mut() {
-                        match diff_kind {
-                            DiffKind::Subtract => {
-                                *size = size.saturating_sub(entry_diff);
-                            }
+        let size_diff = entry.size.unwrap_or(0);
+        let ignored_diff = entry.ignored_size;
+        let file_types: HashMap<String, u64> = match entry.kind {
+            FolderEntryType::Folder => self
+                .store
+                .get_folder(&entry_path.to_path_buf())
+                .map(|folder| folder.file_type_map.clone())
+                .unwrap_or_default(),
+            _ => entry_path
+                .extension()
+                .map(|extension| extension.to_string_lossy().to_string())
+                .map(|extension| HashMap::from([(extension, size_diff)]))
+                .unwrap_or_default(),
+        };
+        let update_file_types = |folder: &mut Folder| match diff_kind {
+            DiffKind::Add => folder.add_file_types(&file_types),
+            DiffKind::Subtract => folder.subtract_file_types(&file_types),
+        };
+
+        // Folder holding the entry is updated by caller, sizes are kept by its ancestors
+        let mut child_path = match entry_path.parent() {
+            Some(parent) => parent.to_path_buf(),
+            None => return,
+        };
+        // File types are kept by every folder containing the entry
+        if let Some(folder) = self.store.get_folder_mut(&child_path) {
+            update_file_types(folder);
+        }
+        while let (Some(parent_path), Some(title)) = (child_path.parent(), child_path.file_name()) {
+            let parent_path = parent_path.to_path_buf();
+            let title = title.to_string_lossy();
+            let Some(parent_folder) = self.store.get_folder_mut(&parent_path) else {
+                break;
+            };
+            if let Some(parent_entry) = parent_folder
+                .entries
+                .iter_mut()
+                .find(|e| e.kind == FolderEntryType::Folder && e.title == title)
+            {
+                match diff_kind {
+                    DiffKind::Add => {
+                        parent_entry.increment_size(size_diff);
+                        parent_entry.ignored_size += ignored_diff;
+                    }
+                    DiffKind::Subtract => {
+                        if let Some(size) = parent_entry.size.as_mut() {
+                            *size = size.saturating_sub(size_diff);
                         }
+                        parent_entry.ignored_size =
+                            parent_entry.ignored_size.saturating_sub(ignored_diff);
                     }
                 }
-                parent_folder.sorted_by = None;
-                parent_path = parent.to_path_buf();
-            } else {
-                break;
             }
+            update_file_types(parent_folder);
+            parent_folder.sorted_by = None;
+            child_path = parent_path;
+        }
+    }
+
+    /// Lines of `<bytes>\t<path>` for every simulated deletion followed by their total,
+    /// `None` outside of dry-run mode
+    pub fn dry_run_summary(&self) -> Option<String> {
+        if !self.ui_config.dry_run {
+            return None;
+        }
+        let mut total = 0;
+        let mut lines = vec![];
+        for task in &self.dry_run_deletions {
+            let size = task.entry.size.unwrap_or(0);
+            total += size;
+            lines.push(format!("{size}\t{}", task.path().to_string_lossy()));
         }
+        lines.push(format!("{total}\ttotal"));
+        Some(lines.join("\n"))
     }
 
     pub fn toggle_debug(&mut self) {
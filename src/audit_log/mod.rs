@@ -0,0 +1,187 @@
+use globset::{Glob, GlobMatcher};
+use serde::{Deserialize, Serialize};
+use std::fs::{create_dir_all, read_to_string, OpenOptions};
+use std::io::{self, ErrorKind, Write};
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+pub const AUDIT_LOG_DIR: &str = "wiper";
+pub const AUDIT_LOG_FILE_NAME: &str = "deletions.log";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditEntryKind {
+    File,
+    Folder,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditMethod {
+    Trash,
+    Permanent,
+}
+
+impl AuditMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditMethod::Trash => "trash",
+            AuditMethod::Permanent => "permanent",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AuditStatus {
+    Deleted,
+    /// Stopped by user, entry may be removed partially
+    Cancelled,
+    Failed,
+}
+
+impl AuditStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            AuditStatus::Deleted => "deleted",
+            AuditStatus::Cancelled => "cancelled",
+            AuditStatus::Failed => "failed",
+        }
+    }
+}
+
+/// Single deletion, stored as one JSON line
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// RFC 3339 time in UTC
+    pub timestamp: String,
+    pub path: PathBuf,
+    pub size: Option<u64>,
+    pub kind: AuditEntryKind,
+    pub method: AuditMethod,
+    pub status: AuditStatus,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl AuditRecord {
+    pub fn time(&self) -> Option<SystemTime> {
+        humantime::parse_rfc3339_weak(&self.timestamp).ok()
+    }
+
+    /// Tab-separated line printed by `wiper log`
+    pub fn to_line(&self) -> String {
+        let mut line = format!(
+            "{}\t{}\t{}\t{}\t{}",
+            self.timestamp,
+            self.status.as_str(),
+            self.method.as_str(),
+            self.size.map(|size| size.to_string()).unwrap_or("-".into()),
+            self.path.to_string_lossy()
+        );
+        if let Some(error) = &self.error {
+            line.push_str(&format!("\t{error}"));
+        }
+        line
+    }
+}
+
+pub fn format_timestamp(time: SystemTime) -> String {
+    humantime::format_rfc3339_seconds(time).to_string()
+}
+
+/// Append-only file with every deletion
+#[derive(Debug, Clone)]
+pub struct AuditLog {
+    pub path: PathBuf,
+}
+
+impl AuditLog {
+    pub fn new(path: PathBuf) -> Self {
+        AuditLog { path }
+    }
+
+    pub fn append(&self, record: &AuditRecord) -> io::Result<()> {
+        if let Some(dir) = self.path.parent() {
+            create_dir_all(dir)?;
+        }
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())
+    }
+
+    /// Reads records in order they were written. Missing file has no records,
+    /// malformed lines are skipped
+    pub fn read(&self) -> io::Result<Vec<AuditRecord>> {
+        match read_to_string(&self.path) {
+            Ok(content) => Ok(content
+                .lines()
+                .filter_map(|line| serde_json::from_str(line).ok())
+                .collect()),
+            Err(err) if err.kind() == ErrorKind::NotFound => Ok(vec![]),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+/// Selects records shown by `wiper log`
+#[derive(Debug, Clone, Default)]
+pub struct AuditFilter {
+    pub since: Option<SystemTime>,
+    /// Matched against absolute path of the entry
+    pub path: Option<GlobMatcher>,
+    pub failed_only: bool,
+}
+
+impl AuditFilter {
+    pub fn matches(&self, record: &AuditRecord) -> bool {
+        let is_recent = self
+            .since
+            .is_none_or(|since| record.time().is_some_and(|time| time >= since));
+        let is_matching_path = self
+            .path
+            .as_ref()
+            .is_none_or(|glob| glob.is_match(&record.path));
+
+        is_recent
+            && is_matching_path
+            && (!self.failed_only || record.status != AuditStatus::Deleted)
+    }
+}
+
+/// Parses relative duration (`7days`, `12h`) or date (`2024-05-01`, `2024-05-01T10:00:00Z`)
+pub fn parse_since(value: &str) -> Result<SystemTime, String> {
+    if let Ok(duration) = humantime::parse_duration(value) {
+        return SystemTime::now()
+            .checked_sub(duration)
+            .ok_or_else(|| format!("'{value}' is too far in the past"));
+    }
+    let timestamp = match value.len() {
+        10 => format!("{value}T00:00:00Z"),
+        _ => value.to_string(),
+    };
+    humantime::parse_rfc3339_weak(&timestamp).map_err(|_| {
+        format!("expected duration like '7days' or date like '2024-05-01', got '{value}'")
+    })
+}
+
+/// Parses glob matching whole path
+pub fn parse_path_glob(pattern: &str) -> Result<GlobMatcher, String> {
+    Glob::new(pattern)
+        .map(|glob| glob.compile_matcher())
+        .map_err(|err| err.to_string())
+}
+
+/// Default log location: `$XDG_STATE_HOME/wiper/deletions.log` or `$HOME/.local/state/wiper/deletions.log`
+pub fn default_audit_log_path(env: &impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    let state_home = match env("XDG_STATE_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env("HOME")?).join(".local").join("state"),
+    };
+
+    Some(state_home.join(AUDIT_LOG_DIR).join(AUDIT_LOG_FILE_NAME))
+}
@@ -0,0 +1,188 @@
+use crate::audit_log::{parse_path_glob, parse_since, AuditFilter};
+use crate::export::ExportFormat;
+use crate::fs::{parse_glob, SizeMode, SortBy};
+use clap::{Args, Parser, Subcommand};
+use globset::GlobMatcher;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Command-line arguments
+#[derive(Debug, Parser)]
+#[command(name = "wiper", version, about)]
+pub struct Cli {
+    /// Directory to scan, defaults to the current one
+    pub path: Option<String>,
+
+    /// Config file, defaults to $XDG_CONFIG_HOME/wiper/config.toml
+    #[arg(long, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Show space usage with gradient
+    #[arg(short, long, overrides_with = "no_colored")]
+    pub colored: bool,
+
+    /// Show space usage without gradient
+    #[arg(long, overrides_with = "colored")]
+    pub no_colored: bool,
+
+    /// Initial sorting order
+    #[arg(short, long, value_name = "ORDER")]
+    pub sort: Option<SortBy>,
+
+    /// Move removed content to Trash bin
+    #[arg(short, long, overrides_with = "no_trash")]
+    pub trash: bool,
+
+    /// Remove content permanently
+    #[arg(long, overrides_with = "trash")]
+    pub no_trash: bool,
+
+    /// Open files with the default system app on Enter
+    #[arg(long, overrides_with = "no_open_file")]
+    pub open_file: bool,
+
+    /// Do nothing when Enter is pressed on a file
+    #[arg(long, overrides_with = "open_file")]
+    pub no_open_file: bool,
+
+    /// Show debug panel
+    #[arg(long, overrides_with = "no_debug")]
+    pub debug: bool,
+
+    /// Hide debug panel
+    #[arg(long, overrides_with = "debug")]
+    pub no_debug: bool,
+
+    /// Do not descend into folders located on other filesystems
+    #[arg(short = 'x', long, overrides_with = "no_one_file_system")]
+    pub one_file_system: bool,
+
+    /// Descend into folders located on other filesystems
+    #[arg(long, overrides_with = "one_file_system")]
+    pub no_one_file_system: bool,
+
+    /// Classify entries as tracked, ignored or untracked by git
+    #[arg(short, long, overrides_with = "no_git")]
+    pub git: bool,
+
+    /// Do not classify entries by git
+    #[arg(long, overrides_with = "git")]
+    pub no_git: bool,
+
+    /// Simulate deletions without touching the filesystem, print them on exit
+    #[arg(long, overrides_with = "no_dry_run")]
+    pub dry_run: bool,
+
+    /// Delete entries for real
+    #[arg(long, overrides_with = "dry_run")]
+    pub no_dry_run: bool,
+
+    /// Keep scanned tree up to date with changes on disk, needs `watch` feature
+    #[arg(
+        short,
+        long,
+        overrides_with = "no_watch",
+        conflicts_with_all = ["import", "diff", "export"]
+    )]
+    pub watch: bool,
+
+    /// Do not watch for changes after the scan
+    #[arg(long, overrides_with = "watch")]
+    pub no_watch: bool,
+
+    /// Show the previous scan at once and reread only modified folders, save the scan on exit
+    #[arg(
+        long,
+        overrides_with = "no_cache",
+        conflicts_with_all = ["import", "diff", "export"]
+    )]
+    pub cache: bool,
+
+    /// Always scan from scratch
+    #[arg(long, overrides_with = "cache")]
+    pub no_cache: bool,
+
+    /// Scan cache folder, defaults to $XDG_CACHE_HOME/wiper
+    #[arg(long, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Skip entries matching glob pattern, can be repeated
+    #[arg(short, long, value_name = "GLOB", value_parser = parse_glob)]
+    pub exclude: Vec<String>,
+
+    /// Never delete entries matching glob pattern, can be repeated
+    #[arg(long, value_name = "GLOB", value_parser = parse_glob)]
+    pub protect: Vec<String>,
+
+    /// Report apparent file sizes or allocated disk blocks
+    #[arg(long, value_name = "MODE")]
+    pub size_mode: Option<SizeMode>,
+
+    /// Scan without UI and print the result in provided format
+    #[arg(long, value_name = "FORMAT")]
+    pub export: Option<ExportFormat>,
+
+    /// Write export to file instead of stdout
+    #[arg(short, long, value_name = "FILE", requires = "export")]
+    pub output: Option<PathBuf>,
+
+    /// Browse ncdu JSON dump instead of scanning, nothing can be deleted
+    #[arg(long, value_name = "FILE", conflicts_with = "path")]
+    pub import: Option<PathBuf>,
+
+    /// Compare two snapshots made with `--export snapshot`, nothing can be deleted
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["OLD", "NEW"],
+        conflicts_with_all = ["path", "import", "export"]
+    )]
+    pub diff: Option<Vec<PathBuf>>,
+
+    /// Deletion log, defaults to $XDG_STATE_HOME/wiper/deletions.log
+    #[arg(long, value_name = "FILE", global = true)]
+    pub audit_log: Option<PathBuf>,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Clone, Subcommand)]
+pub enum Command {
+    /// Print deletions recorded in the audit log
+    Log(LogArgs),
+}
+
+#[derive(Debug, Clone, Args)]
+pub struct LogArgs {
+    /// Only show deletions after duration ago (e.g. 7days) or date (e.g. 2024-05-01)
+    #[arg(long, value_name = "WHEN", value_parser = parse_since)]
+    pub since: Option<SystemTime>,
+
+    /// Only show deletions of paths matching glob pattern
+    #[arg(long, value_name = "GLOB", value_parser = parse_path_glob)]
+    pub path: Option<GlobMatcher>,
+
+    /// Only show deletions which failed or were cancelled
+    #[arg(long)]
+    pub failed: bool,
+}
+
+impl LogArgs {
+    pub fn filter(&self) -> AuditFilter {
+        AuditFilter {
+            since: self.since,
+            path: self.path.clone(),
+            failed_only: self.failed,
+        }
+    }
+}
+
+/// Collapses a pair of `--flag` / `--no-flag` switches into an optional value
+pub fn flag(enabled: bool, disabled: bool) -> Option<bool> {
+    match (enabled, disabled) {
+        (true, _) => Some(true),
+        (_, true) => Some(false),
+        _ => None,
+    }
+}
@@ -0,0 +1,89 @@
+use clap::ValueEnum;
+use std::path::PathBuf;
+
+use super::{ConfigError, InitConfig};
+
+pub const ENV_CONFIG: &str = "WIPER_CONFIG";
+pub const ENV_COLORED: &str = "WIPER_COLORED";
+pub const ENV_SORT_BY: &str = "WIPER_SORT_BY";
+pub const ENV_MOVE_TO_TRASH: &str = "WIPER_MOVE_TO_TRASH";
+pub const ENV_OPEN_FILE: &str = "WIPER_OPEN_FILE";
+pub const ENV_DEBUG_ENABLED: &str = "WIPER_DEBUG_ENABLED";
+pub const ENV_SIZE_MODE: &str = "WIPER_SIZE_MODE";
+pub const ENV_ONE_FILE_SYSTEM: &str = "WIPER_ONE_FILE_SYSTEM";
+pub const ENV_GIT: &str = "WIPER_GIT";
+pub const ENV_DRY_RUN: &str = "WIPER_DRY_RUN";
+pub const ENV_WATCH: &str = "WIPER_WATCH";
+pub const ENV_AUDIT_LOG: &str = "WIPER_AUDIT_LOG";
+pub const ENV_CACHE: &str = "WIPER_CACHE";
+pub const ENV_CACHE_DIR: &str = "WIPER_CACHE_DIR";
+
+fn parse_bool(name: &str, value: String) -> Result<bool, ConfigError> {
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" => Ok(false),
+        _ => Err(ConfigError::Env {
+            name: name.to_string(),
+            message: format!("expected boolean, got '{value}'"),
+        }),
+    }
+}
+
+fn read_bool(
+    env: &impl Fn(&str) -> Option<String>,
+    name: &str,
+) -> Result<Option<bool>, ConfigError> {
+    env(name).map(|value| parse_bool(name, value)).transpose()
+}
+
+fn read_enum<T: ValueEnum>(
+    env: &impl Fn(&str) -> Option<String>,
+    name: &str,
+) -> Result<Option<T>, ConfigError> {
+    env(name)
+        .map(|value| {
+            T::from_str(&value, true).map_err(|_| {
+                let expected: Vec<String> = T::value_variants()
+                    .iter()
+                    .filter_map(|variant| variant.to_possible_value())
+                    .map(|variant| format!("'{}'", variant.get_name()))
+                    .collect();
+                ConfigError::Env {
+                    name: name.to_string(),
+                    message: format!("expected {}, got '{value}'", expected.join(" or ")),
+                }
+            })
+        })
+        .transpose()
+}
+
+/// Reads `WIPER_*` environment variables
+pub fn from_env(env: &impl Fn(&str) -> Option<String>) -> Result<InitConfig, ConfigError> {
+    Ok(InitConfig {
+        file_path: None,
+        colored: read_bool(env, ENV_COLORED)?,
+        sort_by: read_enum(env, ENV_SORT_BY)?,
+        move_to_trash: read_bool(env, ENV_MOVE_TO_TRASH)?,
+        open_file: read_bool(env, ENV_OPEN_FILE)?,
+        debug_enabled: read_bool(env, ENV_DEBUG_ENABLED)?,
+        size_mode: read_enum(env, ENV_SIZE_MODE)?,
+        one_file_system: read_bool(env, ENV_ONE_FILE_SYSTEM)?,
+        git: read_bool(env, ENV_GIT)?,
+        dry_run: read_bool(env, ENV_DRY_RUN)?,
+        watch: read_bool(env, ENV_WATCH)?,
+        cache: read_bool(env, ENV_CACHE)?,
+        exclude: vec![],
+        protect: vec![],
+        audit_log: env(ENV_AUDIT_LOG)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from),
+        cache_dir: env(ENV_CACHE_DIR)
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from),
+        command: None,
+        export: None,
+        output: None,
+        import: None,
+        diff: None,
+    })
+}
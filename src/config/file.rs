@@ -0,0 +1,105 @@
+use crate::fs::{parse_glob, SizeMode, SortBy};
+use serde::Deserialize;
+use std::fs::read_to_string;
+use std::io::ErrorKind;
+use std::path::{Path, PathBuf};
+
+use super::{ConfigError, InitConfig};
+
+pub const CONFIG_DIR: &str = "wiper";
+pub const CONFIG_FILE_NAME: &str = "config.toml";
+
+/// Contents of the persistent user configuration file
+#[derive(Debug, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    pub colored: Option<bool>,
+    pub sort_by: Option<SortBy>,
+    pub move_to_trash: Option<bool>,
+    pub open_file: Option<bool>,
+    pub debug_enabled: Option<bool>,
+    pub size_mode: Option<SizeMode>,
+    pub one_file_system: Option<bool>,
+    pub git: Option<bool>,
+    pub dry_run: Option<bool>,
+    pub watch: Option<bool>,
+    pub cache: Option<bool>,
+    pub exclude: Option<Vec<String>>,
+    pub protect: Option<Vec<String>>,
+    pub audit_log: Option<PathBuf>,
+    pub cache_dir: Option<PathBuf>,
+}
+
+impl ConfigFile {
+    pub fn parse(path: &Path, content: &str) -> Result<ConfigFile, ConfigError> {
+        let config: ConfigFile = toml::from_str(content).map_err(|err| ConfigError::File {
+            path: path.to_path_buf(),
+            message: err.message().to_string(),
+        })?;
+
+        for pattern in config.exclude.iter().flatten() {
+            parse_glob(pattern).map_err(|message| ConfigError::File {
+                path: path.to_path_buf(),
+                message: format!("invalid exclude pattern '{pattern}': {message}"),
+            })?;
+        }
+        for pattern in config.protect.iter().flatten() {
+            parse_glob(pattern).map_err(|message| ConfigError::File {
+                path: path.to_path_buf(),
+                message: format!("invalid protect pattern '{pattern}': {message}"),
+            })?;
+        }
+
+        Ok(config)
+    }
+
+    /// Reads config file. Missing file is only an error when it was requested explicitly
+    pub fn load(path: &Path, required: bool) -> Result<ConfigFile, ConfigError> {
+        match read_to_string(path) {
+            Ok(content) => Self::parse(path, &content),
+            Err(err) if err.kind() == ErrorKind::NotFound && !required => Ok(ConfigFile::default()),
+            Err(err) => Err(ConfigError::File {
+                path: path.to_path_buf(),
+                message: err.to_string(),
+            }),
+        }
+    }
+}
+
+impl From<ConfigFile> for InitConfig {
+    fn from(file: ConfigFile) -> Self {
+        InitConfig {
+            file_path: None,
+            colored: file.colored,
+            sort_by: file.sort_by,
+            move_to_trash: file.move_to_trash,
+            open_file: file.open_file,
+            debug_enabled: file.debug_enabled,
+            size_mode: file.size_mode,
+            one_file_system: file.one_file_system,
+            git: file.git,
+            dry_run: file.dry_run,
+            watch: file.watch,
+            cache: file.cache,
+            exclude: file.exclude.unwrap_or_default(),
+            protect: file.protect.unwrap_or_default(),
+            audit_log: file.audit_log,
+            cache_dir: file.cache_dir,
+            command: None,
+            export: None,
+            output: None,
+            import: None,
+            diff: None,
+        }
+    }
+}
+
+/// Default config location: `$XDG_CONFIG_HOME/wiper/config.toml` or `$HOME/.config/wiper/config.toml`
+pub fn default_config_path(env: &impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    let config_home = match env("XDG_CONFIG_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env("HOME")?).join(".config"),
+    };
+
+    Some(config_home.join(CONFIG_DIR).join(CONFIG_FILE_NAME))
+}
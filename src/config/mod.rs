@@ -1,16 +1,213 @@
-use crate::fs::SortBy;
+use crate::audit_log::default_audit_log_path;
+use crate::export::ExportFormat;
+use crate::fs::{SizeMode, SortBy};
+use crate::scan_cache::default_cache_dir;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use std::env;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process;
 
+mod cli;
+mod env_vars;
+mod file;
+use cli::flag;
+pub use cli::{Cli, Command, LogArgs};
+pub use env_vars::{
+    from_env, ENV_AUDIT_LOG, ENV_CACHE, ENV_CACHE_DIR, ENV_COLORED, ENV_CONFIG, ENV_DEBUG_ENABLED,
+    ENV_DRY_RUN, ENV_GIT, ENV_MOVE_TO_TRASH, ENV_ONE_FILE_SYSTEM, ENV_OPEN_FILE, ENV_SIZE_MODE,
+    ENV_SORT_BY, ENV_WATCH,
+};
+pub use file::{default_config_path, ConfigFile};
+
+/// Startup configuration. Unset values fall back to [`UIConfig`] defaults
+#[derive(Debug, Default)]
 pub struct InitConfig {
     pub file_path: Option<String>,
+    pub colored: Option<bool>,
+    pub sort_by: Option<SortBy>,
+    pub move_to_trash: Option<bool>,
+    pub open_file: Option<bool>,
+    pub debug_enabled: Option<bool>,
+    pub size_mode: Option<SizeMode>,
+    pub one_file_system: Option<bool>,
+    pub git: Option<bool>,
+    pub dry_run: Option<bool>,
+    /// Store follows changes on disk after the scan
+    pub watch: Option<bool>,
+    /// Previous scan is loaded from cache and the scan is saved on exit
+    pub cache: Option<bool>,
+    /// Glob patterns of entries to skip, collected from every source
+    pub exclude: Vec<String>,
+    /// Glob patterns of entries which can not be deleted, collected from every source
+    pub protect: Vec<String>,
+    /// File every deletion is appended to, nothing is recorded when unset
+    pub audit_log: Option<PathBuf>,
+    /// Folder scan caches are stored in
+    pub cache_dir: Option<PathBuf>,
+    /// Subcommand to run instead of the UI
+    pub command: Option<Command>,
+    /// Scan without UI and print the result
+    pub export: Option<ExportFormat>,
+    /// File export is written to, stdout when unset
+    pub output: Option<PathBuf>,
+    /// ncdu dump opened instead of scanning
+    pub import: Option<PathBuf>,
+    /// Older and newer snapshot compared instead of scanning
+    pub diff: Option<Vec<PathBuf>>,
+}
+
+#[derive(Debug)]
+pub enum ConfigError {
+    /// Invalid command-line arguments, also used for `--help` and `--version`
+    Args(clap::Error),
+    /// Unreadable or malformed config file
+    File { path: PathBuf, message: String },
+    /// Malformed environment variable
+    Env { name: String, message: String },
+    /// Exclude or protect pattern which is not a valid glob
+    Pattern(globset::Error),
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Args(err) => write!(f, "{err}"),
+            ConfigError::File { path, message } => {
+                write!(f, "Invalid config file {}: {message}", path.display())
+            }
+            ConfigError::Env { name, message } => {
+                write!(f, "Invalid environment variable {name}: {message}")
+            }
+            ConfigError::Pattern(err) => write!(f, "Invalid pattern: {err}"),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl From<clap::Error> for ConfigError {
+    fn from(err: clap::Error) -> Self {
+        ConfigError::Args(err)
+    }
+}
+
+impl From<globset::Error> for ConfigError {
+    fn from(err: globset::Error) -> Self {
+        ConfigError::Pattern(err)
+    }
+}
+
+impl ConfigError {
+    /// Prints error and exits with non-zero code (zero for `--help` and `--version`)
+    pub fn exit(&self) -> ! {
+        match self {
+            ConfigError::Args(err) => err.exit(),
+            _ => {
+                eprintln!("{self}");
+                process::exit(1);
+            }
+        }
+    }
 }
 
 impl InitConfig {
-    pub fn build(mut args: impl Iterator<Item = String>) -> Result<InitConfig, &'static str> {
-        args.next();
+    pub fn build(args: impl Iterator<Item = String>) -> Result<InitConfig, ConfigError> {
+        Self::build_with_env(args, |name| env::var(name).ok())
+    }
+
+    /// Merges config file, environment variables and arguments, in that order of precedence
+    pub fn build_with_env(
+        args: impl Iterator<Item = String>,
+        env: impl Fn(&str) -> Option<String>,
+    ) -> Result<InitConfig, ConfigError> {
+        let cli = Cli::try_parse_from(args)?;
+
+        if let Some(path) = &cli.path {
+            if !Path::new(path).is_dir() {
+                return Err(Cli::command()
+                    .error(
+                        ErrorKind::ValueValidation,
+                        format!("'{path}' is not an existing directory"),
+                    )
+                    .into());
+            }
+        }
+
+        let file_config = match cli
+            .config
+            .clone()
+            .or_else(|| env(ENV_CONFIG).map(PathBuf::from))
+        {
+            Some(path) => ConfigFile::load(&path, true)?,
+            None => match default_config_path(&env) {
+                Some(path) => ConfigFile::load(&path, false)?,
+                None => ConfigFile::default(),
+            },
+        };
+
+        let cli_config = InitConfig {
+            file_path: cli.path,
+            colored: flag(cli.colored, cli.no_colored),
+            sort_by: cli.sort,
+            move_to_trash: flag(cli.trash, cli.no_trash),
+            open_file: flag(cli.open_file, cli.no_open_file),
+            debug_enabled: flag(cli.debug, cli.no_debug),
+            size_mode: cli.size_mode,
+            one_file_system: flag(cli.one_file_system, cli.no_one_file_system),
+            git: flag(cli.git, cli.no_git),
+            dry_run: flag(cli.dry_run, cli.no_dry_run),
+            watch: flag(cli.watch, cli.no_watch),
+            cache: flag(cli.cache, cli.no_cache),
+            exclude: cli.exclude,
+            protect: cli.protect,
+            audit_log: cli.audit_log,
+            cache_dir: cli.cache_dir,
+            command: cli.command,
+            export: cli.export,
+            output: cli.output,
+            import: cli.import,
+            diff: cli.diff,
+        };
+
+        let mut config = InitConfig::from(file_config)
+            .merge(from_env(&env)?)
+            .merge(cli_config);
+        if config.audit_log.is_none() {
+            config.audit_log = default_audit_log_path(&env);
+        }
+        if config.cache_dir.is_none() {
+            config.cache_dir = default_cache_dir(&env);
+        }
+        Ok(config)
+    }
 
-        Ok(InitConfig {
-            file_path: args.next(),
-        })
+    /// Overrides values with ones set in `other`, exclude and protect patterns are combined
+    pub fn merge(self, other: InitConfig) -> InitConfig {
+        InitConfig {
+            file_path: other.file_path.or(self.file_path),
+            colored: other.colored.or(self.colored),
+            sort_by: other.sort_by.or(self.sort_by),
+            move_to_trash: other.move_to_trash.or(self.move_to_trash),
+            open_file: other.open_file.or(self.open_file),
+            debug_enabled: other.debug_enabled.or(self.debug_enabled),
+            size_mode: other.size_mode.or(self.size_mode),
+            one_file_system: other.one_file_system.or(self.one_file_system),
+            git: other.git.or(self.git),
+            dry_run: other.dry_run.or(self.dry_run),
+            watch: other.watch.or(self.watch),
+            cache: other.cache.or(self.cache),
+            exclude: [self.exclude, other.exclude].concat(),
+            protect: [self.protect, other.protect].concat(),
+            audit_log: other.audit_log.or(self.audit_log),
+            cache_dir: other.cache_dir.or(self.cache_dir),
+            command: other.command.or(self.command),
+            export: other.export.or(self.export),
+            output: other.output.or(self.output),
+            import: other.import.or(self.import),
+            diff: other.diff.or(self.diff),
+        }
     }
 }
 
@@ -18,10 +215,73 @@ impl InitConfig {
 pub struct UIConfig {
     pub colored: bool,
     pub confirming_deletion: bool,
+    /// Pending confirmation is for permanent deletion of entries which failed to go to trash
+    pub confirming_permanent: bool,
+    /// Error list is shown instead of folder content
+    pub show_errors: bool,
+    /// Largest files of the whole tree are shown instead of folder content
+    pub show_largest: bool,
     pub sort_by: SortBy,
     pub move_to_trash: bool,
     pub open_file: bool,
     pub debug_enabled: bool,
+    pub size_mode: SizeMode,
+    /// Show git status and ignored sizes
+    pub git: bool,
+    /// Deletions are only simulated
+    pub dry_run: bool,
+    /// Session is loaded from a dump, the filesystem is never touched
+    pub read_only: bool,
+    /// Session compares two snapshots, entries show size delta
+    pub diff: bool,
+}
+
+impl UIConfig {
+    pub fn new(config: &InitConfig) -> Self {
+        let default = Self::default();
+
+        UIConfig {
+            colored: config.colored.unwrap_or(default.colored),
+            confirming_deletion: false,
+            confirming_permanent: false,
+            show_errors: false,
+            show_largest: false,
+            sort_by: match (&config.sort_by, &config.diff) {
+                (Some(sort_by), _) => sort_by.clone(),
+                (None, Some(_)) => SortBy::Delta,
+                (None, None) => default.sort_by,
+            },
+            move_to_trash: config.move_to_trash.unwrap_or(default.move_to_trash),
+            open_file: config.open_file.unwrap_or(default.open_file),
+            debug_enabled: config.debug_enabled.unwrap_or(default.debug_enabled),
+            size_mode: config.size_mode.clone().unwrap_or(default.size_mode),
+            git: config.git.unwrap_or(default.git),
+            dry_run: config.dry_run.unwrap_or(default.dry_run),
+            read_only: config.import.is_some() || config.diff.is_some(),
+            diff: config.diff.is_some(),
+        }
+    }
+}
+
+impl Default for UIConfig {
+    fn default() -> Self {
+        UIConfig {
+            colored: false,
+            confirming_deletion: false,
+            confirming_permanent: false,
+            show_errors: false,
+            show_largest: false,
+            sort_by: SortBy::Title,
+            move_to_trash: true,
+            open_file: true,
+            debug_enabled: false,
+            size_mode: SizeMode::default(),
+            git: false,
+            dry_run: false,
+            read_only: false,
+            diff: false,
+        }
+    }
 }
 
 pub const EVENT_INTERVAL: u64 = 100;
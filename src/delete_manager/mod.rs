@@ -0,0 +1,288 @@
+use crate::fs::{delete_file, delete_folder, FolderEntry, FolderEntryType, SizeMode};
+use crate::task_manager::PROGRESS_INTERVAL;
+use crossbeam::channel::{Receiver, Sender};
+use std::fs::{read_dir, remove_dir, remove_file};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+/// Entry of a folder which should be removed from disk
+#[derive(Debug, Clone)]
+pub struct DeleteTask {
+    /// Folder holding the entry
+    pub folder_path: PathBuf,
+    pub entry: FolderEntry,
+}
+
+impl DeleteTask {
+    pub fn path(&self) -> PathBuf {
+        self.folder_path.join(&self.entry.title)
+    }
+}
+
+/// Counters of current batch. Entries moved to trash are counted as a single file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeleteProgress {
+    pub files: u64,
+    pub bytes: u64,
+    pub tasks_done: usize,
+    pub tasks_total: usize,
+}
+
+/// Entry which could not be deleted
+#[derive(Debug, Clone)]
+pub struct DeleteFailure {
+    pub task: DeleteTask,
+    pub kind: io::ErrorKind,
+    pub message: String,
+    /// Trash backend failed, entry can still be deleted permanently
+    pub in_trash: bool,
+}
+
+#[derive(Debug)]
+pub enum DeleteOutcome {
+    Deleted,
+    /// Stopped before the entry was fully removed
+    Cancelled,
+    Failed(io::Error),
+}
+
+#[derive(Debug)]
+pub enum DeleteEvent {
+    Progress(DeleteProgress),
+    Done(DeleteTask, DeleteOutcome),
+    Finished,
+}
+
+#[derive(Debug)]
+pub struct DeleteManager {
+    pub event_tx: Sender<DeleteEvent>,
+    pub event_rx: Receiver<DeleteEvent>,
+    pub is_working: bool,
+    /// Current batch is moved to trash
+    pub move_to_trash: bool,
+    /// Deletions are simulated, the filesystem is never touched
+    pub dry_run: bool,
+    /// Sizes of files removed one by one are reported the same way as the tree shows them
+    pub size_mode: SizeMode,
+    pub progress: DeleteProgress,
+    /// Stops current batch when set
+    cancel_token: Arc<AtomicBool>,
+}
+
+impl DeleteManager {
+    pub fn new() -> Self {
+        let (event_tx, event_rx) = crossbeam::channel::bounded(100);
+        Self {
+            event_tx,
+            event_rx,
+            is_working: false,
+            move_to_trash: false,
+            dry_run: false,
+            size_mode: SizeMode::default(),
+            progress: DeleteProgress::default(),
+            cancel_token: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    pub fn is_done(&self) -> bool {
+        !self.is_working
+    }
+
+    /// Stops permanent deletion after the file being removed, remaining tasks are skipped
+    pub fn cancel(&mut self) {
+        self.cancel_token.store(true, Ordering::Relaxed);
+    }
+
+    pub fn start(&mut self, tasks: Vec<DeleteTask>, move_to_trash: bool) {
+        self.is_working = true;
+        self.move_to_trash = move_to_trash;
+        self.progress = DeleteProgress {
+            tasks_total: tasks.len(),
+            ..DeleteProgress::default()
+        };
+        self.cancel_token = Arc::new(AtomicBool::new(false));
+
+        let event_tx = self.event_tx.clone();
+        let cancel_token = self.cancel_token.clone();
+        let mut progress = self.progress.clone();
+        let dry_run = self.dry_run;
+        let size_mode = self.size_mode.clone();
+        let _ = std::thread::Builder::new()
+            .name("wiper-delete".to_string())
+            .spawn(move || {
+                let mut reported_at = Instant::now();
+                for task in tasks {
+                    let mut on_removed = |bytes: u64| {
+                        progress.files += 1;
+                        progress.bytes += bytes;
+                        if reported_at.elapsed() >= PROGRESS_INTERVAL {
+                            let _ = event_tx.send(DeleteEvent::Progress(progress.clone()));
+                            reported_at = Instant::now();
+                        }
+                    };
+                    let outcome = match cancel_token.load(Ordering::Relaxed) {
+                        true => DeleteOutcome::Cancelled,
+                        false if dry_run => {
+                            // Simulated entry is counted as a single file
+                            on_removed(task.entry.size.unwrap_or(0));
+                            DeleteOutcome::Deleted
+                        }
+                        false => Self::delete(
+                            &task,
+                            move_to_trash,
+                            &size_mode,
+                            &cancel_token,
+                            &mut on_removed,
+                        ),
+                    };
+
+                    progress.tasks_done += 1;
+                    let _ = event_tx.send(DeleteEvent::Progress(progress.clone()));
+                    if event_tx.send(DeleteEvent::Done(task, outcome)).is_err() {
+                        return;
+                    }
+                }
+                let _ = event_tx.send(DeleteEvent::Finished);
+            });
+    }
+
+    fn delete(
+        task: &DeleteTask,
+        move_to_trash: bool,
+        size_mode: &SizeMode,
+        cancel_token: &AtomicBool,
+        on_removed: &mut impl FnMut(u64),
+    ) -> DeleteOutcome {
+        let path = task.path();
+        let result = match (&task.entry.kind, move_to_trash) {
+            (FolderEntryType::Parent, _) => Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "parent entry can not be deleted",
+            )),
+            (FolderEntryType::Folder, false) => {
+                match remove_tree(&path, size_mode, cancel_token, on_removed) {
+                    Ok(true) => Ok(()),
+                    Ok(false) => return DeleteOutcome::Cancelled,
+                    Err(err) => Err(err),
+                }
+            }
+            (FolderEntryType::Folder, true) => {
+                delete_folder(&path, true).map(|_| on_removed(task.entry.size.unwrap_or(0)))
+            }
+            (FolderEntryType::File, _) => {
+                delete_file(&path, move_to_trash).map(|_| on_removed(task.entry.size.unwrap_or(0)))
+            }
+        };
+
+        match result {
+            Ok(_) => DeleteOutcome::Deleted,
+            Err(err) => DeleteOutcome::Failed(err),
+        }
+    }
+
+    /// Collects events of the worker, returns tasks which are finished
+    pub fn process_results(&mut self) -> Vec<(DeleteTask, DeleteOutcome)> {
+        let mut finished = vec![];
+        while let Ok(event) = self.event_rx.try_recv() {
+            match event {
+                DeleteEvent::Progress(progress) => self.progress = progress,
+                DeleteEvent::Done(task, outcome) => finished.push((task, outcome)),
+                DeleteEvent::Finished => self.is_working = false,
+            }
+        }
+        finished
+    }
+}
+
+impl Default for DeleteManager {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Removes folder bottom-up reporting size of every removed file.
+/// Returns `false` when cancelled, the folder is partially removed then
+fn remove_tree(
+    path: &Path,
+    size_mode: &SizeMode,
+    cancel_token: &AtomicBool,
+    on_removed: &mut impl FnMut(u64),
+) -> io::Result<bool> {
+    for entry in read_dir(path)? {
+        if cancel_token.load(Ordering::Relaxed) {
+            return Ok(false);
+        }
+        let entry_path = entry?.path();
+        let metadata = entry_path.symlink_metadata()?;
+        if metadata.is_dir() {
+            if !remove_tree(&entry_path, size_mode, cancel_token, on_removed)? {
+                return Ok(false);
+            }
+        } else {
+            remove_file(&entry_path)?;
+            on_removed(size_mode.size_of(&metadata));
+        }
+    }
+    remove_dir(path)?;
+    Ok(true)
+}
+
+/// Moves the most recently trashed entry of every provided path back to its location,
+/// trash is listed once for the whole batch
+#[cfg(all(
+    unix,
+    not(target_os = "macos"),
+    not(target_os = "ios"),
+    not(target_os = "android")
+))]
+pub fn restore_from_trash(paths: &[PathBuf]) -> Vec<io::Result<()>> {
+    use trash::os_limited::{list, restore_all};
+
+    let mut items = match list() {
+        Ok(items) => items,
+        Err(err) => {
+            let message = err.to_string();
+            return paths
+                .iter()
+                .map(|_| Err(io::Error::other(message.clone())))
+                .collect();
+        }
+    };
+    paths
+        .iter()
+        .map(|path| {
+            let index = items
+                .iter()
+                .enumerate()
+                .filter(|(_, item)| item.original_path() == *path)
+                .max_by_key(|(_, item)| item.time_deleted)
+                .map(|(index, _)| index)
+                .ok_or_else(|| {
+                    io::Error::new(io::ErrorKind::NotFound, "entry is not found in trash")
+                })?;
+            restore_all([items.swap_remove(index)]).map_err(io::Error::other)
+        })
+        .collect()
+}
+
+/// Restoring is only supported for freedesktop trash
+#[cfg(not(all(
+    unix,
+    not(target_os = "macos"),
+    not(target_os = "ios"),
+    not(target_os = "android")
+)))]
+pub fn restore_from_trash(paths: &[PathBuf]) -> Vec<io::Result<()>> {
+    paths
+        .iter()
+        .map(|_| {
+            Err(io::Error::new(
+                io::ErrorKind::Unsupported,
+                "restoring from trash is not supported on this platform",
+            ))
+        })
+        .collect()
+}
@@ -39,9 +39,18 @@ pub fn handle_key_events<S: DataStore<DataStoreKey>>(
         KeyCode::Char('e') => {
             app.on_open_file_explorer();
         }
+        KeyCode::Char('E') => {
+            app.on_toggle_errors();
+        }
+        KeyCode::Char('L') => {
+            app.on_toggle_largest();
+        }
         KeyCode::Char('r') => {
             app.reset();
         }
+        KeyCode::Char('R') => {
+            app.on_refresh_current();
+        }
         KeyCode::Char('d') => {
             if key_event.modifiers == KeyModifiers::CONTROL {
                 app.toggle_debug();
@@ -49,9 +58,27 @@ pub fn handle_key_events<S: DataStore<DataStoreKey>>(
                 app.on_delete();
             }
         }
+        KeyCode::Char(' ') => {
+            app.on_toggle_mark();
+        }
+        KeyCode::Char('a') => {
+            app.on_mark_all();
+        }
+        KeyCode::Char('i') => {
+            app.on_invert_marks();
+        }
+        KeyCode::Char('p') => {
+            app.on_retry_permanently();
+        }
+        KeyCode::Char('u') => {
+            app.on_undo();
+        }
         KeyCode::Char('t') => {
             app.on_toggle_move_to_trash();
         }
+        KeyCode::Char('b') => {
+            app.on_toggle_size_mode();
+        }
         _ => {}
     }
     Ok(())
@@ -0,0 +1,157 @@
+use crate::fs::{DataStore, DataStoreKey, Folder, FolderEntry, FolderEntryType, ScanError};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+mod ncdu;
+pub use ncdu::{read_ncdu, write_ncdu};
+
+/// Format of a headless scan report
+#[derive(Debug, Clone, PartialEq, clap::ValueEnum)]
+pub enum ExportFormat {
+    Json,
+    /// Dump which can be opened with `ncdu -f` or `wiper --import`
+    Ncdu,
+    /// Compact list of paths and sizes, compared with `wiper --diff`
+    Snapshot,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportEntryKind {
+    File,
+    Folder,
+}
+
+/// Entry of exported tree, folders hold their content
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportEntry {
+    pub name: String,
+    pub path: PathBuf,
+    pub kind: ExportEntryKind,
+    /// Unknown for excluded folders
+    pub size: Option<u64>,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub is_excluded: bool,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    pub has_error: bool,
+    /// Size of files inside folder by extension
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file_types: Option<BTreeMap<String, u64>>,
+    /// Missing for files and folders which were not scanned
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<ExportEntry>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportError {
+    pub path: PathBuf,
+    pub kind: String,
+    pub message: String,
+}
+
+impl From<&ScanError> for ExportError {
+    fn from(error: &ScanError) -> Self {
+        ExportError {
+            path: error.path.clone(),
+            kind: format!("{:?}", error.kind),
+            message: error.message.clone(),
+        }
+    }
+}
+
+/// Whole scan, root folder with every nested entry and paths which could not be read
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ExportTree {
+    pub root: ExportEntry,
+    pub errors: Vec<ExportError>,
+}
+
+impl ExportTree {
+    pub fn from_store<S: DataStore<DataStoreKey>>(store: &S, root: &Path) -> Self {
+        let root_path = root.to_path_buf();
+        let name = root
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_else(|| root.to_string_lossy().to_string());
+        let folder = store.get_folder(&root_path);
+
+        ExportTree {
+            root: ExportEntry {
+                name,
+                size: folder.map(Folder::get_size),
+                has_error: folder.is_some_and(|folder| folder.has_error),
+                ..folder_to_export(store, &root_path, folder)
+            },
+            errors: store.get_errors().iter().map(ExportError::from).collect(),
+        }
+    }
+
+    pub fn write_json(&self, writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer_pretty(writer, self)?;
+        Ok(())
+    }
+}
+
+/// Folder entry without name, size and flags, which are known to its parent
+fn folder_to_export<S: DataStore<DataStoreKey>>(
+    store: &S,
+    path: &Path,
+    folder: Option<&Folder>,
+) -> ExportEntry {
+    let children = folder.map(|folder| {
+        let mut entries: Vec<&FolderEntry> = folder
+            .entries
+            .iter()
+            .filter(|entry| entry.kind != FolderEntryType::Parent)
+            .collect();
+        entries.sort();
+        entries
+            .into_iter()
+            .map(|entry| entry_to_export(store, path, entry))
+            .collect()
+    });
+
+    ExportEntry {
+        name: String::new(),
+        path: path.to_path_buf(),
+        kind: ExportEntryKind::Folder,
+        size: None,
+        is_excluded: false,
+        has_error: false,
+        file_types: folder.map(|folder| folder.file_type_map.clone().into_iter().collect()),
+        children,
+    }
+}
+
+fn entry_to_export<S: DataStore<DataStoreKey>>(
+    store: &S,
+    folder_path: &Path,
+    entry: &FolderEntry,
+) -> ExportEntry {
+    let path = folder_path.join(&entry.title);
+    let base = match entry.kind {
+        // Excluded folders are not traversed, their records are empty
+        FolderEntryType::Folder if entry.is_excluded => folder_to_export(store, &path, None),
+        FolderEntryType::Folder => folder_to_export(store, &path, store.get_folder(&path)),
+        _ => ExportEntry {
+            name: String::new(),
+            path,
+            kind: ExportEntryKind::File,
+            size: None,
+            is_excluded: false,
+            has_error: false,
+            file_types: None,
+            children: None,
+        },
+    };
+
+    ExportEntry {
+        name: entry.title.clone(),
+        size: entry.size,
+        is_excluded: entry.is_excluded,
+        has_error: entry.has_error,
+        ..base
+    }
+}
@@ -0,0 +1,238 @@
+use crate::fs::{
+    DataStore, DataStoreKey, Folder, FolderEntry, FolderEntryType, ScanError, SizeMode,
+};
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const NCDU_MAJOR_VERSION: u64 = 1;
+const NCDU_MINOR_VERSION: u64 = 2;
+
+/// ncdu keeps both sizes, wiper only the one of current size mode
+fn size_key(size_mode: &SizeMode) -> &'static str {
+    match size_mode {
+        SizeMode::Apparent => "asize",
+        SizeMode::Allocated => "dsize",
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid ncdu dump: {message}"),
+    )
+}
+
+/// Writes tree under provided root in ncdu JSON format
+pub fn write_ncdu<S: DataStore<DataStoreKey>>(
+    store: &S,
+    root: &Path,
+    size_mode: &SizeMode,
+    writer: impl Write,
+) -> io::Result<()> {
+    let timestamp = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or(0);
+    let dump = json!([
+        NCDU_MAJOR_VERSION,
+        NCDU_MINOR_VERSION,
+        {
+            "progname": "wiper",
+            "progver": env!("CARGO_PKG_VERSION"),
+            "timestamp": timestamp,
+        },
+        folder_to_ncdu(store, root, root.to_string_lossy().to_string(), size_mode),
+    ]);
+    serde_json::to_writer(writer, &dump)?;
+    Ok(())
+}
+
+/// Folder is an array of its own info followed by its entries
+fn folder_to_ncdu<S: DataStore<DataStoreKey>>(
+    store: &S,
+    path: &Path,
+    name: String,
+    size_mode: &SizeMode,
+) -> Value {
+    let folder = store.get_folder(&path.to_path_buf());
+    let mut info = Map::new();
+    info.insert("name".into(), name.into());
+    if folder.is_some_and(|folder| folder.has_error) {
+        info.insert("read_error".into(), true.into());
+    }
+
+    let mut items = vec![Value::Object(info)];
+    let mut entries: Vec<&FolderEntry> = folder
+        .map(|folder| folder.entries.iter().collect())
+        .unwrap_or_default();
+    entries.sort();
+    for entry in entries {
+        let mut item = Map::new();
+        item.insert("name".into(), entry.title.clone().into());
+        match entry.kind {
+            FolderEntryType::Parent => continue,
+            FolderEntryType::Folder if entry.is_excluded => {
+                item.insert("excluded".into(), "pattern".into());
+            }
+            FolderEntryType::Folder => {
+                items.push(folder_to_ncdu(
+                    store,
+                    &path.join(&entry.title),
+                    entry.title.clone(),
+                    size_mode,
+                ));
+                continue;
+            }
+            FolderEntryType::File => {
+                item.insert(size_key(size_mode).into(), entry.size.unwrap_or(0).into());
+                if entry.has_error {
+                    item.insert("read_error".into(), true.into());
+                }
+            }
+        }
+        items.push(Value::Object(item));
+    }
+
+    Value::Array(items)
+}
+
+/// Totals of a folder loaded from dump, added to its parent
+#[derive(Default)]
+struct LoadedFolder {
+    size: u64,
+    hard_links: usize,
+}
+
+/// Fills store with folders of ncdu dump, returns path of its root
+pub fn read_ncdu<S: DataStore<DataStoreKey>>(
+    reader: impl Read,
+    size_mode: &SizeMode,
+    store: &mut S,
+) -> io::Result<PathBuf> {
+    let dump: Value = serde_json::from_reader(reader)?;
+    let root = dump
+        .as_array()
+        .filter(|dump| dump.first().and_then(Value::as_u64) == Some(NCDU_MAJOR_VERSION))
+        .and_then(|dump| dump.get(3))
+        .and_then(Value::as_array)
+        .ok_or_else(|| invalid_data("expected [1, minor, metadata, root]"))?;
+    let root_path = root
+        .first()
+        .and_then(|info| info.get("name"))
+        .and_then(Value::as_str)
+        .map(PathBuf::from)
+        .ok_or_else(|| invalid_data("root folder has no name"))?;
+
+    let mut seen_hard_links = HashSet::new();
+    load_folder(root, &root_path, size_mode, store, &mut seen_hard_links)?;
+    Ok(root_path)
+}
+
+fn load_folder<S: DataStore<DataStoreKey>>(
+    items: &[Value],
+    path: &Path,
+    size_mode: &SizeMode,
+    store: &mut S,
+    seen_hard_links: &mut HashSet<(u64, u64)>,
+) -> io::Result<LoadedFolder> {
+    let info = items
+        .first()
+        .ok_or_else(|| invalid_data("folder has no info"))?;
+    let title = path
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| path.to_string_lossy().to_string());
+    let mut folder = Folder::new(title);
+    if info.get("read_error").and_then(Value::as_bool) == Some(true) {
+        folder.has_error = true;
+        store.add_error(ScanError {
+            path: path.to_path_buf(),
+            kind: io::ErrorKind::Other,
+            message: "folder could not be read when dump was created".into(),
+        });
+    }
+
+    let mut loaded = LoadedFolder::default();
+    for item in items.iter().skip(1) {
+        let (info, children) = match item {
+            Value::Array(children) => (children.first(), Some(children)),
+            info => (Some(info), None),
+        };
+        let info = info
+            .and_then(Value::as_object)
+            .ok_or_else(|| invalid_data("entry is not an object"))?;
+        let name = info
+            .get("name")
+            .and_then(Value::as_str)
+            .ok_or_else(|| invalid_data("entry has no name"))?;
+        let mut entry = FolderEntry {
+            title: name.to_string(),
+            is_loaded: true,
+            has_error: info.get("read_error").and_then(Value::as_bool) == Some(true),
+            ..FolderEntry::default()
+        };
+
+        if let Some(children) = children {
+            let child = load_folder(
+                children,
+                &path.join(name),
+                size_mode,
+                store,
+                seen_hard_links,
+            )?;
+            entry.kind = FolderEntryType::Folder;
+            entry.size = Some(child.size);
+            loaded.size += child.size;
+            loaded.hard_links += child.hard_links;
+        } else if info.contains_key("excluded") {
+            // Excluded entries are not traversed, like excluded folders of a scan
+            entry.kind = FolderEntryType::Folder;
+            entry.is_excluded = true;
+        } else {
+            let mut size = info
+                .get(size_key(size_mode))
+                .and_then(Value::as_u64)
+                .unwrap_or(0);
+            let hard_link_id = info
+                .get("ino")
+                .and_then(Value::as_u64)
+                .filter(|_| info.get("hlnkc").and_then(Value::as_bool) == Some(true));
+            if let Some(ino) = hard_link_id {
+                let dev = info.get("dev").and_then(Value::as_u64).unwrap_or(0);
+                // Count every hard-linked inode only once
+                if !seen_hard_links.insert((dev, ino)) {
+                    size = 0;
+                    loaded.hard_links += 1;
+                }
+            }
+            if let Some(extension) = Path::new(name).extension() {
+                folder.append_file_type_size(&extension.to_string_lossy().to_string(), size);
+            }
+            entry.size = Some(size);
+            loaded.size += size;
+        }
+        folder.entries.push(entry);
+    }
+
+    // Extension sizes of nested files are counted in every ancestor
+    for entry in folder.entries.clone() {
+        if entry.kind != FolderEntryType::Folder || entry.is_excluded {
+            continue;
+        }
+        let child_path = path.join(&entry.title);
+        let file_types = store
+            .get_folder(&child_path)
+            .map(|child| child.file_type_map.clone())
+            .unwrap_or_default();
+        for (extension, size) in file_types {
+            folder.append_file_type_size(&extension, size);
+        }
+    }
+    folder.hard_links = loaded.hard_links;
+    store.set_folder(&path.to_path_buf(), folder);
+
+    Ok(loaded)
+}
@@ -0,0 +1,96 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::fs::read_to_string;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+/// Per-folder file with exclude patterns, one glob per line
+pub const IGNORE_FILE_NAME: &str = ".wiperignore";
+
+/// Patterns from an ignore file, matched relative to the folder it is located in
+#[derive(Debug)]
+struct IgnoreFile {
+    base: PathBuf,
+    globs: GlobSet,
+}
+
+/// Glob patterns which exclude entries from traversal
+#[derive(Debug, Clone, Default)]
+pub struct ExcludeFilter {
+    /// Patterns the filter was built from, ignore files excluded
+    patterns: Vec<String>,
+    globs: GlobSet,
+    ignore_files: Vec<Arc<IgnoreFile>>,
+}
+
+fn build_glob_set<'a>(patterns: impl Iterator<Item = &'a str>) -> Result<GlobSet, globset::Error> {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        builder.add(Glob::new(pattern)?);
+    }
+    builder.build()
+}
+
+fn is_match(globs: &GlobSet, path: &Path) -> bool {
+    path.file_name().is_some_and(|name| globs.is_match(name)) || globs.is_match(path)
+}
+
+impl ExcludeFilter {
+    pub fn new(patterns: &[String]) -> Result<Self, globset::Error> {
+        Ok(ExcludeFilter {
+            patterns: patterns.to_vec(),
+            globs: build_glob_set(patterns.iter().map(String::as_str))?,
+            ignore_files: vec![],
+        })
+    }
+
+    /// Returns filter extended with ignore file from provided folder, if there is a valid one
+    pub fn with_ignore_file(&self, folder: &Path) -> Self {
+        let mut filter = self.clone();
+
+        if let Ok(content) = read_to_string(folder.join(IGNORE_FILE_NAME)) {
+            // Invalid lines are skipped, so one typo does not disable the whole file
+            let patterns = content
+                .lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .filter(|line| Glob::new(line).is_ok());
+            if let Ok(globs) = build_glob_set(patterns) {
+                filter.ignore_files.push(Arc::new(IgnoreFile {
+                    base: folder.to_path_buf(),
+                    globs,
+                }));
+            }
+        }
+
+        filter
+    }
+
+    /// Returns filter extended with ignore files of every folder above provided path
+    pub fn for_path(&self, path: &Path) -> Self {
+        let mut folders: Vec<&Path> = path.ancestors().skip(1).collect();
+        folders.reverse();
+        folders.into_iter().fold(self.clone(), |filter, folder| {
+            filter.with_ignore_file(folder)
+        })
+    }
+
+    pub fn patterns(&self) -> &[String] {
+        &self.patterns
+    }
+
+    /// Patterns are matched against entry name and its path
+    pub fn is_excluded(&self, path: &Path) -> bool {
+        is_match(&self.globs, path)
+            || self.ignore_files.iter().any(|ignore_file| {
+                path.strip_prefix(&ignore_file.base)
+                    .is_ok_and(|relative| is_match(&ignore_file.globs, relative))
+            })
+    }
+}
+
+/// Checks that provided pattern is a valid glob
+pub fn parse_glob(pattern: &str) -> Result<String, String> {
+    Glob::new(pattern)
+        .map(|_| pattern.to_string())
+        .map_err(|err| err.to_string())
+}
@@ -3,6 +3,7 @@ use crate::ui::constants::TEXT_PARENT_DIR;
 use crate::fs::folder_entry::{FolderEntry, FolderEntryType};
 use std::cmp::Ordering;
 use std::collections::HashMap;
+use std::time::SystemTime;
 
 use super::SortBy;
 
@@ -14,6 +15,10 @@ pub struct Folder {
     pub entries: Vec<FolderEntry>,
     pub has_error: bool,
     pub file_type_map: HashMap<String, u64>,
+    /// Amount of hard links inside which were not counted towards size
+    pub hard_links: usize,
+    /// Modification time of the folder when its content was read
+    pub modified: Option<SystemTime>,
 }
 
 impl Folder {
@@ -27,9 +32,12 @@ impl Folder {
                 title: String::from(TEXT_PARENT_DIR),
                 size: None,
                 is_loaded: true,
+                ..FolderEntry::default()
             }],
             has_error: false,
             file_type_map: HashMap::new(),
+            hard_links: 0,
+            modified: None,
         }
     }
 
@@ -39,6 +47,12 @@ impl Folder {
             .fold(0, |acc, entry| acc + entry.size.unwrap_or(0))
     }
 
+    pub fn get_ignored_size(&self) -> u64 {
+        self.entries
+            .iter()
+            .fold(0, |acc, entry| acc + entry.ignored_size)
+    }
+
     pub fn get_selected_entry_size(&self) -> u64 {
         self.get_selected_entry().size.unwrap_or(0)
     }
@@ -48,6 +62,53 @@ impl Folder {
         self.cursor_index = self.cursor_index.min(self.entries.len() - 1);
     }
 
+    /// Removes entry by title, keeping cursor in bounds
+    pub fn remove_entry(&mut self, title: &str) {
+        if let Some(index) = self
+            .entries
+            .iter()
+            .position(|e| e.kind != FolderEntryType::Parent && e.title == title)
+        {
+            self.entries.remove(index);
+            if index < self.cursor_index {
+                self.cursor_index -= 1;
+            }
+            self.cursor_index = self.cursor_index.min(self.entries.len() - 1);
+        }
+    }
+
+    pub fn get_marked_entries(&self) -> Vec<&FolderEntry> {
+        self.entries.iter().filter(|e| e.is_marked).collect()
+    }
+
+    pub fn get_marked_size(&self) -> u64 {
+        self.get_marked_entries()
+            .iter()
+            .fold(0, |acc, entry| acc + entry.size.unwrap_or(0))
+    }
+
+    pub fn toggle_selected_mark(&mut self) {
+        if let Some(entry) = self.entries.get_mut(self.cursor_index) {
+            if entry.kind != FolderEntryType::Parent {
+                entry.is_marked = !entry.is_marked;
+            }
+        }
+    }
+
+    pub fn mark_all(&mut self) {
+        for entry in self.entries.iter_mut() {
+            entry.is_marked = entry.kind != FolderEntryType::Parent;
+        }
+    }
+
+    pub fn invert_marks(&mut self) {
+        for entry in self.entries.iter_mut() {
+            if entry.kind != FolderEntryType::Parent {
+                entry.is_marked = !entry.is_marked;
+            }
+        }
+    }
+
     pub fn get_selected_entry(&self) -> &FolderEntry {
         if let Some(entry) = self.entries.get(self.cursor_index) {
             entry
@@ -108,18 +169,61 @@ impl Folder {
         });
     }
 
+    /// Most grown entries first, entries without change last
+    pub fn sort_by_delta(&mut self) {
+        self.entries.sort_by(|a, b| {
+            if a.kind == FolderEntryType::Parent || b.kind == FolderEntryType::Parent {
+                return (b.kind == FolderEntryType::Parent)
+                    .cmp(&(a.kind == FolderEntryType::Parent));
+            }
+            match (a.change, b.change) {
+                (Some(change_a), Some(change_b)) => change_b.delta.cmp(&change_a.delta),
+                (Some(_), None) => Ordering::Less,
+                (None, Some(_)) => Ordering::Greater,
+                (None, None) => Ordering::Equal,
+            }
+        });
+    }
+
+    /// Bytes added to folder since older snapshot
+    pub fn get_delta(&self) -> i64 {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.change)
+            .fold(0, |acc, change| acc + change.delta)
+    }
+
     pub fn append_file_type_size(&mut self, file_type: &String, size: u64) {
         let total_size = self.file_type_map.entry(file_type.to_owned()).or_insert(0);
         *total_size += size;
     }
 
+    /// Adds sizes of nested entry which appeared after the scan
+    pub fn add_file_types(&mut self, file_types: &HashMap<String, u64>) {
+        for (file_type, size) in file_types {
+            self.append_file_type_size(file_type, *size);
+        }
+    }
+
+    /// Removes sizes of nested entry which is removed or scanned again
+    pub fn subtract_file_types(&mut self, file_types: &HashMap<String, u64>) {
+        for (file_type, size) in file_types {
+            if let Some(total_size) = self.file_type_map.get_mut(file_type) {
+                *total_size = total_size.saturating_sub(*size);
+                if *total_size == 0 {
+                    self.file_type_map.remove(file_type);
+                }
+            }
+        }
+    }
+
     fn get_sorted_file_types_by_size(&self) -> Vec<(String, u64)> {
         let mut file_types: Vec<(String, u64)> = self
             .file_type_map
             .iter()
             .map(|(k, &v)| (k.clone(), v))
             .collect();
-        file_types.sort_by(|a, b| b.1.cmp(&a.1));
+        file_types.sort_by_key(|file_type| std::cmp::Reverse(file_type.1));
         file_types
     }
 
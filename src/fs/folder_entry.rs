@@ -1,8 +1,10 @@
+use crate::fs::GitStatus;
 use std::cmp::Ordering;
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
 pub enum FolderEntryType {
     Parent,
+    #[default]
     File,
     Folder,
 }
@@ -27,12 +29,61 @@ impl PartialOrd for FolderEntryType {
     }
 }
 
-#[derive(Debug, Clone, Eq, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeChangeKind {
+    Added,
+    Removed,
+    Grown,
+    Shrunk,
+    Unchanged,
+}
+
+/// Difference of entry size between two snapshots
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SizeChange {
+    pub kind: SizeChangeKind,
+    /// Bytes added since older snapshot, negative if removed
+    pub delta: i64,
+}
+
+impl SizeChange {
+    pub fn new(old_size: Option<u64>, new_size: Option<u64>) -> Self {
+        let delta = new_size.unwrap_or(0) as i64 - old_size.unwrap_or(0) as i64;
+        let kind = match (old_size, new_size) {
+            (None, _) => SizeChangeKind::Added,
+            (_, None) => SizeChangeKind::Removed,
+            _ if delta > 0 => SizeChangeKind::Grown,
+            _ if delta < 0 => SizeChangeKind::Shrunk,
+            _ => SizeChangeKind::Unchanged,
+        };
+        SizeChange { kind, delta }
+    }
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
 pub struct FolderEntry {
     pub title: String,
     pub size: Option<u64>,
     pub kind: FolderEntryType,
     pub is_loaded: bool,
+    /// Folder is located on another filesystem than its parent
+    pub is_mount_point: bool,
+    /// Folder matches exclude patterns and is not traversed
+    pub is_excluded: bool,
+    /// Set in git mode for entries inside a repository
+    pub git_status: Option<GitStatus>,
+    /// Bytes inside which are ignored by git
+    pub ignored_size: u64,
+    /// Entry could not be read
+    pub has_error: bool,
+    /// Entry is selected for batch deletion
+    pub is_marked: bool,
+    /// Entry matches protected paths and can not be deleted
+    pub is_protected: bool,
+    /// Set in diff mode, removed entries have no size
+    pub change: Option<SizeChange>,
+    /// Entry is loaded from the scan cache and is not revalidated yet
+    pub is_stale: bool,
 }
 
 impl Ord for FolderEntry {
@@ -0,0 +1,296 @@
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::Match;
+use std::collections::HashSet;
+use std::fs::{read, read_to_string};
+use std::io::{Error, ErrorKind, Result};
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+const GIT_DIR: &str = ".git";
+const GITIGNORE_FILE_NAME: &str = ".gitignore";
+
+/// State of an entry relative to git repository it belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum GitStatus {
+    /// File is in the index, or folder contains such files
+    Tracked,
+    /// Entry matches ignore rules
+    Ignored,
+    /// Entry is neither tracked nor ignored
+    Untracked,
+}
+
+/// Git repository, read directly from its files without git binary
+#[derive(Debug)]
+pub struct GitRepo {
+    root: PathBuf,
+    tracked_files: HashSet<PathBuf>,
+    /// Every folder which contains tracked files, including repository root
+    tracked_folders: HashSet<PathBuf>,
+    /// Rules from `.git/info/exclude`
+    info_exclude: Gitignore,
+}
+
+impl GitRepo {
+    /// Opens repository located in provided folder
+    pub fn open(root: &Path) -> Option<GitRepo> {
+        let git_dir = resolve_git_dir(root)?;
+        let object_id_len = match read_to_string(git_dir.join("config")) {
+            Ok(config) if config.contains("sha256") => 32,
+            _ => 20,
+        };
+        // Freshly initialized repositories have no index yet
+        let index_paths = match read(git_dir.join("index")) {
+            Ok(index) => parse_index(&index, object_id_len).ok()?,
+            Err(_) => vec![],
+        };
+
+        let mut tracked_files = HashSet::new();
+        let mut tracked_folders = HashSet::new();
+        tracked_folders.insert(root.to_path_buf());
+        for relative_path in index_paths {
+            let path = root.join(relative_path);
+            for ancestor in path.ancestors().skip(1) {
+                if ancestor == root || !tracked_folders.insert(ancestor.to_path_buf()) {
+                    break;
+                }
+            }
+            tracked_files.insert(path);
+        }
+
+        let mut builder = GitignoreBuilder::new(root);
+        builder.add(git_dir.join("info").join("exclude"));
+
+        Some(GitRepo {
+            root: root.to_path_buf(),
+            tracked_files,
+            tracked_folders,
+            info_exclude: builder.build().unwrap_or_else(|_| Gitignore::empty()),
+        })
+    }
+
+    /// Opens repository which contains provided path
+    pub fn discover(path: &Path) -> Option<GitRepo> {
+        path.ancestors()
+            .find(|ancestor| ancestor.join(GIT_DIR).exists())
+            .and_then(GitRepo::open)
+    }
+
+    fn is_tracked(&self, path: &Path, is_dir: bool) -> bool {
+        if is_dir {
+            self.tracked_folders.contains(path)
+        } else {
+            self.tracked_files.contains(path)
+        }
+    }
+}
+
+/// `.git` is either a folder or a file pointing to it (worktrees, submodules)
+fn resolve_git_dir(root: &Path) -> Option<PathBuf> {
+    let git_path = root.join(GIT_DIR);
+    if git_path.is_dir() {
+        return Some(git_path);
+    }
+    let content = read_to_string(&git_path).ok()?;
+    let git_dir = content.strip_prefix("gitdir:")?.trim();
+    Some(root.join(git_dir))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Result<u32> {
+    data.get(offset..offset + 4)
+        .map(|bytes| u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+        .ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "truncated git index"))
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Result<u16> {
+    data.get(offset..offset + 2)
+        .map(|bytes| u16::from_be_bytes([bytes[0], bytes[1]]))
+        .ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "truncated git index"))
+}
+
+/// Variable-length integer used by index v4 path compression
+fn read_offset(data: &[u8], offset: &mut usize) -> Result<usize> {
+    let truncated = || Error::new(ErrorKind::UnexpectedEof, "truncated git index");
+    let mut byte = *data.get(*offset).ok_or_else(truncated)?;
+    *offset += 1;
+    let mut value = (byte & 0x7f) as usize;
+    while byte & 0x80 != 0 {
+        byte = *data.get(*offset).ok_or_else(truncated)?;
+        *offset += 1;
+        value = ((value + 1) << 7) | (byte & 0x7f) as usize;
+    }
+    Ok(value)
+}
+
+/// Returns paths of entries from git index file (versions 2, 3 and 4)
+pub fn parse_index(data: &[u8], object_id_len: usize) -> Result<Vec<PathBuf>> {
+    if data.get(0..4) != Some(b"DIRC") {
+        return Err(Error::new(ErrorKind::InvalidData, "not a git index"));
+    }
+    let version = read_u32(data, 4)?;
+    if !(2..=4).contains(&version) {
+        return Err(Error::new(
+            ErrorKind::InvalidData,
+            format!("unsupported git index version {version}"),
+        ));
+    }
+    let entries = read_u32(data, 8)?;
+
+    // ctime, mtime, dev, ino, mode, uid, gid, size
+    const STAT_LEN: usize = 40;
+    const EXTENDED_FLAG: u16 = 0x4000;
+
+    let mut paths = Vec::with_capacity(entries as usize);
+    let mut previous_path: Vec<u8> = vec![];
+    let mut offset = 12;
+
+    for _ in 0..entries {
+        let entry_start = offset;
+        offset += STAT_LEN + object_id_len;
+        let flags = read_u16(data, offset)?;
+        offset += 2;
+        if version >= 3 && flags & EXTENDED_FLAG != 0 {
+            offset += 2;
+        }
+
+        let path = if version == 4 {
+            let strip_len = read_offset(data, &mut offset)?;
+            let suffix_len = data
+                .get(offset..)
+                .and_then(|rest| rest.iter().position(|byte| *byte == 0))
+                .ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "truncated git index"))?;
+            let keep_len = previous_path.len().saturating_sub(strip_len);
+            let mut path = previous_path[..keep_len].to_vec();
+            path.extend_from_slice(&data[offset..offset + suffix_len]);
+            offset += suffix_len + 1;
+            path
+        } else {
+            let name_len = data
+                .get(offset..)
+                .and_then(|rest| rest.iter().position(|byte| *byte == 0))
+                .ok_or_else(|| Error::new(ErrorKind::UnexpectedEof, "truncated git index"))?;
+            let path = data[offset..offset + name_len].to_vec();
+            // Entries are padded with 1-8 NUL bytes to a multiple of eight
+            let entry_len = offset - entry_start + name_len;
+            offset = entry_start + (entry_len + 8) / 8 * 8;
+            path
+        };
+
+        paths.push(PathBuf::from(String::from_utf8_lossy(&path).to_string()));
+        previous_path = path;
+    }
+
+    Ok(paths)
+}
+
+/// Classifies entries during traversal. Passed down to subfolders, collecting their ignore files
+#[derive(Debug, Clone, Default)]
+pub struct GitFilter {
+    repo: Option<Arc<GitRepo>>,
+    /// Ignore files from repository root down to current folder
+    gitignores: Vec<Arc<Gitignore>>,
+    /// Current folder is ignored, so is everything untracked inside
+    is_ignored: bool,
+    /// Current folder is inside `.git`
+    is_git_dir: bool,
+}
+
+impl GitFilter {
+    /// Creates filter for provided folder, loading ignore files of its parent folders
+    pub fn for_path(path: &Path) -> Self {
+        let Some(repo) = GitRepo::discover(path) else {
+            return GitFilter::default();
+        };
+        let root = repo.root.clone();
+        let mut filter = GitFilter {
+            repo: Some(Arc::new(repo)),
+            ..GitFilter::default()
+        };
+
+        let mut folders: Vec<&Path> = path
+            .ancestors()
+            .skip(1)
+            .take_while(|ancestor| ancestor.starts_with(&root))
+            .collect();
+        folders.reverse();
+        for folder in folders {
+            filter = filter.enter(folder);
+        }
+
+        filter
+    }
+
+    /// Returns filter for children of provided folder
+    pub fn enter(&self, folder: &Path) -> Self {
+        // Repository found below traversal root, or nested one with its own rules
+        let is_repo_root = self.repo.as_ref().is_some_and(|repo| repo.root == folder);
+        if !self.is_git_dir && !is_repo_root && folder.join(GIT_DIR).exists() {
+            if let Some(repo) = GitRepo::open(folder) {
+                let filter = GitFilter {
+                    repo: Some(Arc::new(repo)),
+                    ..GitFilter::default()
+                };
+                return filter.with_gitignore(folder);
+            }
+        }
+        if self.repo.is_none() {
+            return self.clone();
+        }
+
+        let mut filter = self.clone();
+        match self.status(folder, true) {
+            // Folder with tracked files may still be ignored for the rest of its content
+            Some(_) => filter.is_ignored = self.is_ignored(folder, true),
+            None => filter.is_git_dir = true,
+        }
+        filter.with_gitignore(folder)
+    }
+
+    fn with_gitignore(mut self, folder: &Path) -> Self {
+        let gitignore_path = folder.join(GITIGNORE_FILE_NAME);
+        if gitignore_path.is_file() {
+            let (gitignore, _) = Gitignore::new(gitignore_path);
+            if !gitignore.is_empty() {
+                self.gitignores.push(Arc::new(gitignore));
+            }
+        }
+        self
+    }
+
+    /// Status of an entry inside current folder. None outside of repository and for `.git`
+    pub fn status(&self, path: &Path, is_dir: bool) -> Option<GitStatus> {
+        let repo = self.repo.as_ref()?;
+        if self.is_git_dir
+            || path.file_name().is_some_and(|name| name == GIT_DIR)
+            || !path.starts_with(&repo.root)
+        {
+            return None;
+        }
+        if repo.is_tracked(path, is_dir) {
+            return Some(GitStatus::Tracked);
+        }
+        match self.is_ignored(path, is_dir) {
+            true => Some(GitStatus::Ignored),
+            false => Some(GitStatus::Untracked),
+        }
+    }
+
+    /// Whether entry inside current folder matches ignore rules, regardless of the index
+    fn is_ignored(&self, path: &Path, is_dir: bool) -> bool {
+        if self.is_ignored {
+            return true;
+        }
+        // Deeper ignore files take precedence
+        for gitignore in self.gitignores.iter().rev() {
+            match gitignore.matched(path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        self.repo
+            .as_ref()
+            .is_some_and(|repo| repo.info_exclude.matched(path, is_dir).is_ignore())
+    }
+}
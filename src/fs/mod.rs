@@ -1,28 +1,122 @@
-use crate::config::UIConfig;
 use crate::ui::constants::TEXT_UNKNOWN;
-use std::fs::{read_dir, remove_dir_all, remove_file};
+use std::fs::{read_dir, remove_dir_all, remove_file, Metadata};
 use std::path::PathBuf;
 use trash;
 
+mod exclude;
 mod folder;
 mod folder_entry;
+mod git;
+mod protect;
+mod scan_error;
 mod store;
+pub use exclude::{parse_glob, ExcludeFilter, IGNORE_FILE_NAME};
 pub use folder::Folder;
-pub use folder_entry::{FolderEntry, FolderEntryType};
+pub use folder_entry::{FolderEntry, FolderEntryType, SizeChange, SizeChangeKind};
+pub use git::{parse_index, GitFilter, GitRepo, GitStatus};
+pub use protect::{ProtectFilter, DEFAULT_PROTECTED_PATHS};
+pub use scan_error::ScanError;
 pub use store::{DSHashmap, DataStore, DataStoreKey, DataStoreType};
 
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, clap::ValueEnum, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
 pub enum SortBy {
     Title,
     Size,
+    /// Only available when comparing snapshots
+    #[value(skip)]
+    #[serde(skip)]
+    Delta,
 }
-/// Returns new unsorted folder
-pub fn path_to_folder(path: PathBuf) -> Folder {
+
+/// Which size of a file is reported
+#[derive(
+    Debug, Clone, Default, PartialEq, clap::ValueEnum, serde::Deserialize, serde::Serialize,
+)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeMode {
+    /// Amount of bytes in file (`du --apparent-size`)
+    #[default]
+    Apparent,
+    /// Amount of bytes in allocated disk blocks (`du`)
+    Allocated,
+}
+
+impl SizeMode {
+    pub fn size_of(&self, metadata: &Metadata) -> u64 {
+        match self {
+            SizeMode::Apparent => metadata.len(),
+            SizeMode::Allocated => allocated_size(metadata),
+        }
+    }
+}
+
+/// Bytes taken by allocated blocks. Falls back to apparent size where blocks are unknown
+#[cfg(unix)]
+pub fn allocated_size(metadata: &Metadata) -> u64 {
+    use std::os::unix::fs::MetadataExt;
+    metadata.blocks() * 512
+}
+
+#[cfg(not(unix))]
+pub fn allocated_size(metadata: &Metadata) -> u64 {
+    metadata.len()
+}
+
+/// Unique (device, inode) identity of a file which has more than one hard link
+#[cfg(unix)]
+pub fn hard_link_id(metadata: &Metadata) -> Option<(u64, u64)> {
+    use std::os::unix::fs::MetadataExt;
+    if metadata.is_dir() || metadata.nlink() < 2 {
+        return None;
+    }
+    Some((metadata.dev(), metadata.ino()))
+}
+
+#[cfg(not(unix))]
+pub fn hard_link_id(_metadata: &Metadata) -> Option<(u64, u64)> {
+    None
+}
+
+/// Id of the device which contains provided file
+#[cfg(unix)]
+pub fn device_id(metadata: &Metadata) -> Option<u64> {
+    use std::os::unix::fs::MetadataExt;
+    Some(metadata.dev())
+}
+
+#[cfg(not(unix))]
+pub fn device_id(_metadata: &Metadata) -> Option<u64> {
+    None
+}
+
+/// Options which affect filesystem traversal
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    pub size_mode: SizeMode,
+    /// Do not descend into folders located on other filesystems
+    pub one_file_system: bool,
+    /// Entries which are not traversed
+    pub exclude: ExcludeFilter,
+    /// Entries which can not be deleted
+    pub protect: ProtectFilter,
+    /// Classify entries by git ignore rules
+    pub git: bool,
+}
+
+/// Returns new unsorted folder and errors of paths which could not be read
+pub fn path_to_folder(path: PathBuf, options: &ScanOptions) -> (Folder, Vec<ScanError>) {
     let folder_name = path
         .file_name()
         .and_then(|name| name.to_str())
         .unwrap_or(TEXT_UNKNOWN);
     let mut folder = Folder::new(folder_name.to_string());
+    let mut errors = vec![];
+    let folder_metadata = path.metadata().ok();
+    let folder_device = folder_metadata.as_ref().and_then(device_id);
+    folder.modified = folder_metadata.and_then(|metadata| metadata.modified().ok());
+    let exclude = options.exclude.for_path(&path).with_ignore_file(&path);
+    let git = options.git.then(|| GitFilter::for_path(&path).enter(&path));
 
     match read_dir(path.clone()) {
         Ok(path) => {
@@ -34,16 +128,34 @@ pub fn path_to_folder(path: PathBuf) -> Folder {
                         title: file_name.to_owned(),
                         size: None,
                         is_loaded: true,
+                        ..FolderEntry::default()
                     };
-                    if entry.path().is_dir() {
+                    let is_excluded = exclude.is_excluded(&entry.path());
+                    folder_entry.is_protected = options.protect.is_protected(&entry.path());
+                    let is_dir = entry.path().is_dir();
+                    folder_entry.git_status = git
+                        .as_ref()
+                        .and_then(|git| git.status(&entry.path(), is_dir));
+                    if is_dir {
                         folder_entry.kind = FolderEntryType::Folder;
+                        folder_entry.is_excluded = is_excluded;
+                        folder_entry.is_mount_point = folder_device.is_some()
+                            && entry.path().metadata().ok().and_then(|m| device_id(&m))
+                                != folder_device;
+                    } else if is_excluded {
+                        continue;
                     } else {
                         match entry.metadata() {
                             Ok(metadata) => {
-                                folder_entry.size = Some(metadata.len());
+                                let size = options.size_mode.size_of(&metadata);
+                                folder_entry.size = Some(size);
+                                if folder_entry.git_status == Some(GitStatus::Ignored) {
+                                    folder_entry.ignored_size = size;
+                                }
                             }
-                            Err(_) => {
-                                folder.has_error = true;
+                            Err(err) => {
+                                folder_entry.has_error = true;
+                                errors.push(ScanError::new(&entry.path(), &err));
                             }
                         }
                     }
@@ -51,16 +163,17 @@ pub fn path_to_folder(path: PathBuf) -> Folder {
                 }
             }
         }
-        Err(_) => {
+        Err(err) => {
             folder.has_error = true;
+            errors.push(ScanError::new(&path, &err));
         }
     }
 
-    folder
+    (folder, errors)
 }
 
-pub fn delete_folder(path: &PathBuf, config: &UIConfig) -> std::io::Result<()> {
-    if config.move_to_trash {
+pub fn delete_folder(path: &PathBuf, move_to_trash: bool) -> std::io::Result<()> {
+    if move_to_trash {
         match trash::delete(path) {
             Ok(_) => Ok(()),
             Err(err) => Err(std::io::Error::other(err)),
@@ -71,8 +184,8 @@ pub fn delete_folder(path: &PathBuf, config: &UIConfig) -> std::io::Result<()> {
     }
 }
 
-pub fn delete_file(path: &PathBuf, config: &UIConfig) -> std::io::Result<()> {
-    if config.move_to_trash {
+pub fn delete_file(path: &PathBuf, move_to_trash: bool) -> std::io::Result<()> {
+    if move_to_trash {
         match trash::delete(path) {
             Ok(_) => Ok(()),
             Err(err) => Err(std::io::Error::other(err)),
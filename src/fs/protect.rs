@@ -0,0 +1,65 @@
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::path::{Path, PathBuf};
+
+/// Paths which are always protected, home folder is added separately
+pub const DEFAULT_PROTECTED_PATHS: [&str; 3] = ["/", "/etc", "/usr"];
+
+/// Paths and glob patterns of entries which can never be deleted
+#[derive(Debug, Clone, Default)]
+pub struct ProtectFilter {
+    /// Absolute paths, folders containing them are protected as well
+    paths: Vec<PathBuf>,
+    globs: GlobSet,
+}
+
+/// Protected path as written and its canonical form when it differs, e.g. `/etc` which is a
+/// symlink on macOS. Entry paths are canonical since scan root is
+fn with_canonical(path: PathBuf) -> Vec<PathBuf> {
+    match path.canonicalize() {
+        Ok(canonical) if canonical != path => vec![path, canonical],
+        _ => vec![path],
+    }
+}
+
+/// Replaces leading `~` of pattern with home folder
+fn expand_home(pattern: &str, home: Option<&Path>) -> String {
+    match (pattern.strip_prefix('~'), home) {
+        (Some(rest), Some(home)) if rest.is_empty() || rest.starts_with('/') => {
+            format!("{}{rest}", home.to_string_lossy())
+        }
+        _ => pattern.to_string(),
+    }
+}
+
+impl ProtectFilter {
+    /// Patterns are matched against entry name and its path, absolute patterns without
+    /// wildcards also protect folders containing them
+    pub fn new(patterns: &[String], home: Option<&Path>) -> Result<Self, globset::Error> {
+        let mut paths: Vec<PathBuf> = DEFAULT_PROTECTED_PATHS.iter().map(PathBuf::from).collect();
+        paths.extend(home.map(Path::to_path_buf));
+
+        let mut builder = GlobSetBuilder::new();
+        for pattern in patterns {
+            let pattern = expand_home(pattern, home);
+            builder.add(Glob::new(&pattern)?);
+            if pattern.starts_with('/') && !pattern.contains(['*', '?', '[', '{']) {
+                paths.push(PathBuf::from(pattern));
+            }
+        }
+
+        Ok(ProtectFilter {
+            paths: paths.into_iter().flat_map(with_canonical).collect(),
+            globs: builder.build()?,
+        })
+    }
+
+    pub fn is_protected(&self, path: &Path) -> bool {
+        self.paths
+            .iter()
+            .any(|protected| protected.starts_with(path))
+            || path
+                .file_name()
+                .is_some_and(|name| self.globs.is_match(name))
+            || self.globs.is_match(path)
+    }
+}
@@ -0,0 +1,33 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Path which could not be read during traversal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScanError {
+    pub path: PathBuf,
+    pub kind: io::ErrorKind,
+    pub message: String,
+}
+
+impl ScanError {
+    pub fn new(path: &Path, err: &io::Error) -> Self {
+        ScanError {
+            path: path.to_path_buf(),
+            kind: err.kind(),
+            message: err.to_string(),
+        }
+    }
+
+    /// Falls back to provided path for errors which are not bound to one
+    pub fn from_walk_error(err: &jwalk::Error, fallback_path: &Path) -> Self {
+        let path = err.path().unwrap_or(fallback_path);
+        match err.io_error() {
+            Some(io_error) => ScanError::new(path, io_error),
+            None => ScanError {
+                path: path.to_path_buf(),
+                kind: io::ErrorKind::Other,
+                message: err.to_string(),
+            },
+        }
+    }
+}
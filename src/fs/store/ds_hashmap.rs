@@ -1,5 +1,5 @@
-use crate::fs::{DataStore, Folder, SortBy};
-use std::collections::HashMap;
+use crate::fs::{DataStore, Folder, ScanError, SortBy};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
 use super::DataStoreKey;
@@ -12,6 +12,10 @@ pub struct DSHashmap {
     /// Map for all file paths
     pub store: FileTreeMap,
     pub file_type_map: HashMap<String, u64>,
+    /// Paths which could not be read
+    pub errors: Vec<ScanError>,
+    /// Paths of `errors`, every path is reported once
+    error_paths: HashSet<PathBuf>,
 }
 
 impl DataStore<DataStoreKey> for DSHashmap {
@@ -20,6 +24,8 @@ impl DataStore<DataStoreKey> for DSHashmap {
             current_path: PathBuf::from("."),
             store: HashMap::new(),
             file_type_map: HashMap::new(),
+            errors: vec![],
+            error_paths: HashSet::new(),
         }
     }
 
@@ -47,6 +53,10 @@ impl DataStore<DataStoreKey> for DSHashmap {
         self.store.insert(path.clone(), folder);
     }
 
+    fn get_folder(&self, path: &PathBuf) -> Option<&Folder> {
+        self.store.get(path)
+    }
+
     fn get_folder_mut(&mut self, path: &PathBuf) -> Option<&mut Folder> {
         self.store.get_mut(path)
     }
@@ -62,12 +72,14 @@ impl DataStore<DataStoreKey> for DSHashmap {
                 None => match sort_by {
                     SortBy::Title => folder.sort_by_title(),
                     SortBy::Size => folder.sort_by_size(),
+                    SortBy::Delta => folder.sort_by_delta(),
                 },
                 Some(folder_sort_by) => {
                     if folder_sort_by.clone() != sort_by {
                         match sort_by {
                             SortBy::Title => folder.sort_by_title(),
                             SortBy::Size => folder.sort_by_size(),
+                            SortBy::Delta => folder.sort_by_delta(),
                         };
                     };
                 }
@@ -94,7 +106,7 @@ impl DataStore<DataStoreKey> for DSHashmap {
     }
 
     fn remove_path(&mut self, path: &PathBuf) {
-        self.store.remove(path);
+        self.store.retain(|key, _| !key.starts_with(path));
     }
 
     fn get_nodes_len(&self) -> usize {
@@ -104,4 +116,14 @@ impl DataStore<DataStoreKey> for DSHashmap {
     fn get_keys(&mut self) -> Vec<PathBuf> {
         self.store.keys().cloned().collect()
     }
+
+    fn add_error(&mut self, error: ScanError) {
+        if self.error_paths.insert(error.path.clone()) {
+            self.errors.push(error);
+        }
+    }
+
+    fn get_errors(&self) -> &[ScanError] {
+        &self.errors
+    }
 }
@@ -1,7 +1,7 @@
 mod ds_hashmap;
 pub use ds_hashmap::DSHashmap;
 
-use crate::fs::{Folder, SortBy};
+use crate::fs::{Folder, ScanError, SortBy};
 use std::path::PathBuf;
 
 pub trait DataStore<T> {
@@ -22,6 +22,9 @@ pub trait DataStore<T> {
     /// Get optional current active mutable Folder
     fn get_current_folder_mut(&mut self) -> Option<&mut Folder>;
 
+    /// Get optional Folder for provided path
+    fn get_folder(&self, path: &T) -> Option<&Folder>;
+
     /// Get optional mutable Folder for provided path
     fn get_folder_mut(&mut self, path: &T) -> Option<&mut Folder>;
 
@@ -40,7 +43,7 @@ pub trait DataStore<T> {
     /// Update current active path to child folder by provided title
     fn move_to_child(&mut self, title: &str);
 
-    /// Remove provided path record from store
+    /// Remove provided path record and records of nested paths from store
     fn remove_path(&mut self, path: &T);
 
     /// Get total known size for provided path
@@ -50,6 +53,12 @@ pub trait DataStore<T> {
     fn get_nodes_len(&self) -> usize;
 
     fn get_keys(&mut self) -> Vec<T>;
+
+    /// Record path which could not be read, once per path
+    fn add_error(&mut self, error: ScanError);
+
+    /// Get errors collected during traversal
+    fn get_errors(&self) -> &[ScanError];
 }
 
 pub type DataStoreKey = PathBuf;
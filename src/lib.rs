@@ -19,6 +19,28 @@ pub mod fs;
 /// Task queue manager
 pub mod task_manager;
 
+/// Headless scanning API
+pub mod scanner;
+
+/// Background deletion worker
+pub mod delete_manager;
+
+/// Persistent log of deletions
+pub mod audit_log;
+
+/// Headless scan reports
+pub mod export;
+
+/// Saved scans and comparison between them
+pub mod snapshot;
+
+/// Scanned tree reused between sessions
+pub mod scan_cache;
+
+/// Live updates of scanned tree
+#[cfg(feature = "watch")]
+pub mod watcher;
+
 /// Debug logger
 pub mod logger;
 
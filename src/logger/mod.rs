@@ -28,6 +28,14 @@ impl Logger {
     }
 
     pub fn log(&mut self, message: String) {
+        self.push(MessageLevel::Info, message);
+    }
+
+    pub fn error(&mut self, message: String) {
+        self.push(MessageLevel::Error, message);
+    }
+
+    fn push(&mut self, level: MessageLevel, message: String) {
         let timestamp = SystemTime::now()
             .duration_since(UNIX_EPOCH)
             .expect("Time went backwards")
@@ -36,8 +44,7 @@ impl Logger {
         if self.messages.len() >= 30 {
             self.messages.pop_back();
         }
-        self.messages
-            .push_front((timestamp, MessageLevel::Info, message));
+        self.messages.push_front((timestamp, level, message));
     }
 
     pub fn start_timer(&mut self, name: &str) {
@@ -1,23 +1,129 @@
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 use std::env;
-use std::io;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
 use std::process;
 use wiper::app::{App, AppResult};
-use wiper::config::InitConfig;
+use wiper::audit_log::AuditLog;
 use wiper::config::EVENT_INTERVAL;
+use wiper::config::{Command, InitConfig, LogArgs};
 use wiper::events::{handle_key_events, Event, EventHandler};
-use wiper::fs::DataStoreType;
+use wiper::export::{write_ncdu, ExportFormat, ExportTree};
+use wiper::fs::{DataStore, DataStoreType};
+use wiper::scanner::Scanner;
+use wiper::snapshot::Snapshot;
 use wiper::tui::Tui;
 
-fn main() -> AppResult<()> {
-    let config = InitConfig::build(env::args()).unwrap_or_else(|err| {
-        eprintln!("Problem parsing arguments: {err}");
+/// Prints matching audit log records, oldest first
+fn print_audit_log(config: &InitConfig, args: &LogArgs) -> AppResult<()> {
+    let Some(path) = &config.audit_log else {
+        return Err("Audit log location is unknown, set it with --audit-log".into());
+    };
+    let filter = args.filter();
+    for record in AuditLog::new(path.clone()).read()? {
+        if filter.matches(&record) {
+            println!("{}", record.to_line());
+        }
+    }
+    Ok(())
+}
+
+fn open_diff_or_exit(app: &mut App<DataStoreType>, old_path: &Path, new_path: &Path) {
+    if let Err(err) = app.open_diff(old_path, new_path) {
+        eprintln!(
+            "Failed to compare {} with {}: {err}",
+            old_path.display(),
+            new_path.display()
+        );
         process::exit(1);
-    });
+    }
+}
+
+/// Scanned tree is still usable without live updates, so failure is only logged
+#[cfg(feature = "watch")]
+fn start_watching(app: &mut App<DataStoreType>) {
+    if let Err(err) = app.watch() {
+        app.logger
+            .error(format!("Failed to watch for changes: {err}"));
+    }
+}
 
-    let mut app: App<DataStoreType> = App::new(config);
-    app.init();
+#[cfg(not(feature = "watch"))]
+fn start_watching(_app: &mut App<DataStoreType>) {
+    eprintln!("Watching for changes is not supported, build wiper with `watch` feature");
+    process::exit(1);
+}
+
+fn import_or_exit(app: &mut App<DataStoreType>, path: &Path) {
+    if let Err(err) = app.import(path) {
+        eprintln!("Failed to import {}: {err}", path.display());
+        process::exit(1);
+    }
+}
+
+/// Scans path (or reads dump) without UI and writes the whole tree to output
+fn export_scan(config: InitConfig, format: ExportFormat) -> AppResult<()> {
+    let output = config.output.clone();
+    let import = config.import.clone();
+    let mut app: App<DataStoreType> = App::new(config).unwrap_or_else(|err| err.exit());
+    let size_mode = app.ui_config.size_mode.clone();
+    let (store, root) = match &import {
+        Some(path) => {
+            import_or_exit(&mut app, path);
+            let root = app.store.get_current_path().clone();
+            (app.store, root)
+        }
+        None => {
+            let tree = Scanner::new(app.store.get_current_path())
+                .options(app.task_manager.options.clone())
+                .run()?;
+            (tree.store, tree.root)
+        }
+    };
+
+    let mut writer: Box<dyn Write> = match output {
+        Some(path) => Box::new(BufWriter::new(File::create(path)?)),
+        None => Box::new(io::stdout().lock()),
+    };
+    match format {
+        ExportFormat::Json => ExportTree::from_store(&store, &root).write_json(&mut writer)?,
+        ExportFormat::Ncdu => write_ncdu(&store, &root, &size_mode, &mut writer)?,
+        ExportFormat::Snapshot => {
+            Snapshot::from_store(&store, &root, &size_mode).write(&mut writer)?
+        }
+    }
+    // Dropping a buffered writer loses write errors
+    writer.flush()?;
+    Ok(())
+}
+
+fn main() -> AppResult<()> {
+    let mut config = InitConfig::build(env::args()).unwrap_or_else(|err| err.exit());
+    if let Some(Command::Log(args)) = config.command.take() {
+        return print_audit_log(&config, &args);
+    }
+    if let Some(format) = config.export.clone() {
+        return export_scan(config, format);
+    }
+
+    let import = config.import.clone();
+    let diff = config.diff.clone();
+    let watch = config.watch.unwrap_or(false);
+    let mut app: App<DataStoreType> = App::new(config).unwrap_or_else(|err| err.exit());
+    match (&import, diff.as_deref()) {
+        (Some(path), _) => import_or_exit(&mut app, path),
+        (None, Some([old_path, new_path])) => open_diff_or_exit(&mut app, old_path, new_path),
+        _ => {
+            if !app.load_cache() {
+                app.init();
+            }
+            if watch {
+                start_watching(&mut app);
+            }
+        }
+    }
 
     let backend = CrosstermBackend::new(io::stderr());
     let terminal = Terminal::new(backend)?;
@@ -36,5 +142,13 @@ fn main() -> AppResult<()> {
     }
 
     tui.exit()?;
+
+    if let Err(err) = app.save_cache() {
+        eprintln!("Failed to save scan cache: {err}");
+    }
+
+    if let Some(summary) = app.dry_run_summary() {
+        println!("{summary}");
+    }
     Ok(())
 }
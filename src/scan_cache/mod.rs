@@ -0,0 +1,232 @@
+use crate::audit_log::format_timestamp;
+use crate::fs::{
+    DataStore, DataStoreKey, Folder, FolderEntry, FolderEntryType, GitStatus, ProtectFilter,
+    ScanOptions, SizeMode,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{self, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+mod validator;
+pub use validator::{CacheValidator, CachedFolderState, ValidationEvent};
+
+pub const CACHE_DIR: &str = "wiper";
+
+/// Options the tree was scanned with, cache made with other ones is not used
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CacheKey {
+    pub root: PathBuf,
+    pub size_mode: SizeMode,
+    pub one_file_system: bool,
+    pub git: bool,
+    pub exclude: Vec<String>,
+}
+
+impl CacheKey {
+    pub fn new(root: &Path, options: &ScanOptions) -> Self {
+        CacheKey {
+            root: root.to_path_buf(),
+            size_mode: options.size_mode.clone(),
+            one_file_system: options.one_file_system,
+            git: options.git,
+            exclude: options.exclude.patterns().to_vec(),
+        }
+    }
+}
+
+fn is_zero(value: &u64) -> bool {
+    *value == 0
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedEntry {
+    pub title: String,
+    /// Unknown for excluded folders
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<u64>,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_folder: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_mount_point: bool,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_excluded: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub git_status: Option<GitStatus>,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub ignored_size: u64,
+}
+
+/// Scanned folder, path is relative to cache root
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CachedFolder {
+    pub path: PathBuf,
+    /// Folder is read again when its modification time differs
+    pub modified: Option<SystemTime>,
+    pub file_types: HashMap<String, u64>,
+    #[serde(default)]
+    pub hard_links: usize,
+    pub entries: Vec<CachedEntry>,
+}
+
+/// Scanned tree saved between sessions
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ScanCache {
+    #[serde(flatten)]
+    pub key: CacheKey,
+    /// RFC 3339 time in UTC
+    pub timestamp: String,
+    pub folders: Vec<CachedFolder>,
+}
+
+impl ScanCache {
+    /// Collects every folder record under the key root
+    pub fn from_store<S: DataStore<DataStoreKey>>(store: &S, key: CacheKey) -> Self {
+        let mut folders = vec![];
+        collect_folders(store, &key.root, Path::new(""), &mut folders);
+
+        ScanCache {
+            key,
+            timestamp: format_timestamp(SystemTime::now()),
+            folders,
+        }
+    }
+
+    /// Fills store with cached folders, every entry is marked as stale
+    pub fn to_store<S: DataStore<DataStoreKey>>(&self, store: &mut S, protect: &ProtectFilter) {
+        for cached in self.folders.iter() {
+            let path = self.key.root.join(&cached.path);
+            let title = path
+                .file_name()
+                .map(|name| name.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.to_string_lossy().to_string());
+            let mut folder = Folder::new(title);
+            folder.file_type_map = cached.file_types.clone();
+            folder.hard_links = cached.hard_links;
+            folder.modified = cached.modified;
+            folder
+                .entries
+                .extend(cached.entries.iter().map(|entry| FolderEntry {
+                    title: entry.title.clone(),
+                    size: entry.size,
+                    kind: match entry.is_folder {
+                        true => FolderEntryType::Folder,
+                        false => FolderEntryType::File,
+                    },
+                    is_loaded: true,
+                    is_mount_point: entry.is_mount_point,
+                    is_excluded: entry.is_excluded,
+                    git_status: entry.git_status,
+                    ignored_size: entry.ignored_size,
+                    is_protected: protect.is_protected(&path.join(&entry.title)),
+                    is_stale: true,
+                    ..FolderEntry::default()
+                }));
+            store.set_folder(&path, folder);
+        }
+    }
+
+    /// Folders to check for modifications, parents before their content
+    pub fn folder_states(&self) -> Vec<CachedFolderState> {
+        self.folders
+            .iter()
+            .map(|cached| CachedFolderState {
+                path: self.key.root.join(&cached.path),
+                modified: cached.modified,
+                titles: cached
+                    .entries
+                    .iter()
+                    .map(|entry| entry.title.clone())
+                    .collect(),
+            })
+            .collect()
+    }
+
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+
+    /// Replaces file at once, so an interrupted write does not corrupt the previous cache
+    pub fn write(&self, path: &Path) -> io::Result<()> {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let tmp_path = path.with_extension("tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.flush()?;
+        fs::rename(tmp_path, path)
+    }
+}
+
+fn collect_folders<S: DataStore<DataStoreKey>>(
+    store: &S,
+    path: &Path,
+    relative_path: &Path,
+    folders: &mut Vec<CachedFolder>,
+) {
+    let Some(folder) = store.get_folder(&path.to_path_buf()) else {
+        return;
+    };
+    let mut entries = vec![];
+    let mut children = vec![];
+    for entry in folder.entries.iter() {
+        let is_folder = match entry.kind {
+            FolderEntryType::Parent => continue,
+            FolderEntryType::Folder => true,
+            FolderEntryType::File => false,
+        };
+        if is_folder && !entry.is_excluded {
+            children.push(entry.title.clone());
+        }
+        entries.push(CachedEntry {
+            title: entry.title.clone(),
+            size: entry.size,
+            is_folder,
+            is_mount_point: entry.is_mount_point,
+            is_excluded: entry.is_excluded,
+            git_status: entry.git_status,
+            ignored_size: entry.ignored_size,
+        });
+    }
+    folders.push(CachedFolder {
+        path: relative_path.to_path_buf(),
+        modified: folder.modified,
+        file_types: folder.file_type_map.clone(),
+        hard_links: folder.hard_links,
+        entries,
+    });
+    for title in children {
+        collect_folders(
+            store,
+            &path.join(&title),
+            &relative_path.join(&title),
+            folders,
+        );
+    }
+}
+
+/// Every scanned root has its own file, named after FNV-1a hash of the root path
+pub fn cache_file_path(dir: &Path, root: &Path) -> PathBuf {
+    let hash = root
+        .as_os_str()
+        .as_encoded_bytes()
+        .iter()
+        .fold(0xcbf29ce484222325_u64, |hash, byte| {
+            (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+        });
+    dir.join(format!("{hash:016x}.json"))
+}
+
+/// Default cache location: `$XDG_CACHE_HOME/wiper` or `$HOME/.cache/wiper`
+pub fn default_cache_dir(env: &impl Fn(&str) -> Option<String>) -> Option<PathBuf> {
+    let cache_home = match env("XDG_CACHE_HOME").filter(|dir| !dir.is_empty()) {
+        Some(dir) => PathBuf::from(dir),
+        None => PathBuf::from(env("HOME")?).join(".cache"),
+    };
+
+    Some(cache_home.join(CACHE_DIR))
+}
@@ -0,0 +1,82 @@
+use crossbeam::channel::{unbounded, Receiver};
+use std::collections::BTreeSet;
+use std::fs::read_dir;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+/// Cached folder to compare with the disk
+#[derive(Debug, Clone)]
+pub struct CachedFolderState {
+    pub path: PathBuf,
+    pub modified: Option<SystemTime>,
+    /// Entries of the folder when it was cached
+    pub titles: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ValidationEvent {
+    /// Folder was not modified since it was cached
+    Unchanged(PathBuf),
+    /// Folder was modified or removed, its cached and current entries have to be read again
+    Changed {
+        path: PathBuf,
+        modified: Option<SystemTime>,
+        entries: Vec<PathBuf>,
+    },
+    Finished,
+}
+
+/// Compares modification times of cached folders in background
+#[derive(Debug)]
+pub struct CacheValidator {
+    events: Receiver<ValidationEvent>,
+}
+
+impl CacheValidator {
+    /// Validation stops when validator is dropped
+    pub fn start(folders: Vec<CachedFolderState>) -> Self {
+        let (event_tx, event_rx) = unbounded();
+        let _ = std::thread::Builder::new()
+            .name("wiper-cache-validator".to_string())
+            .spawn(move || {
+                for folder in folders {
+                    if event_tx.send(Self::validate(folder)).is_err() {
+                        return;
+                    }
+                }
+                let _ = event_tx.send(ValidationEvent::Finished);
+            });
+
+        CacheValidator { events: event_rx }
+    }
+
+    fn validate(folder: CachedFolderState) -> ValidationEvent {
+        let modified = folder
+            .path
+            .metadata()
+            .ok()
+            .and_then(|metadata| metadata.modified().ok());
+        if modified.is_some() && modified == folder.modified {
+            return ValidationEvent::Unchanged(folder.path);
+        }
+
+        let mut entries: BTreeSet<PathBuf> = folder
+            .titles
+            .iter()
+            .map(|title| folder.path.join(title))
+            .collect();
+        if let Ok(dir) = read_dir(&folder.path) {
+            entries.extend(dir.flatten().map(|entry| entry.path()));
+        }
+        ValidationEvent::Changed {
+            path: folder.path,
+            modified,
+            entries: entries.into_iter().collect(),
+        }
+    }
+
+    /// Receives events without blocking
+    pub fn take_events(&self) -> Vec<ValidationEvent> {
+        self.events.try_iter().collect()
+    }
+}
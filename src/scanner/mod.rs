@@ -0,0 +1,108 @@
+use crate::fs::{DataStore, DataStoreKey, DataStoreType, Folder, ScanError, ScanOptions};
+use crate::task_manager::{ScanStats, TaskManager};
+use std::env;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::Duration;
+
+/// How often finished entries are collected from traversal threads
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Scans a folder without terminal, UI config or logger.
+///
+/// ```no_run
+/// use wiper::fs::{ScanOptions, SizeMode};
+/// use wiper::scanner::Scanner;
+///
+/// let tree = Scanner::new("/var/log")
+///     .options(ScanOptions {
+///         size_mode: SizeMode::Allocated,
+///         ..ScanOptions::default()
+///     })
+///     .run()?;
+/// println!("{} bytes", tree.size());
+/// # Ok::<(), std::io::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct Scanner {
+    root: PathBuf,
+    options: ScanOptions,
+}
+
+impl Scanner {
+    /// Relative root is resolved against current directory and canonicalized
+    pub fn new(root: impl Into<PathBuf>) -> Self {
+        let root = root.into();
+        let root = match root.is_absolute() {
+            true => root,
+            false => env::current_dir()
+                .map(|current_dir| current_dir.join(&root))
+                .unwrap_or(root),
+        };
+        let root = root.canonicalize().unwrap_or(root);
+
+        Scanner {
+            root,
+            options: ScanOptions::default(),
+        }
+    }
+
+    pub fn options(mut self, options: ScanOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Blocks until the whole tree is scanned, fails when the traversal can not be started
+    pub fn run(self) -> io::Result<ScanTree> {
+        self.run_into(DataStoreType::new(), |_| {})
+    }
+
+    /// Fills provided store, `on_progress` receives live counters while the walk is running
+    pub fn run_into<S: DataStore<DataStoreKey>>(
+        self,
+        mut store: S,
+        mut on_progress: impl FnMut(&ScanStats),
+    ) -> io::Result<ScanTree<S>> {
+        store.set_current_path(&self.root);
+        let mut task_manager = TaskManager::<S>::new(self.options);
+        task_manager.start(vec![self.root.clone()])?;
+        while !task_manager.is_done() {
+            task_manager.process_results(&mut store);
+            on_progress(&task_manager.stats);
+            thread::sleep(POLL_INTERVAL);
+        }
+
+        Ok(ScanTree {
+            root: self.root,
+            store,
+            stats: std::mem::take(&mut task_manager.stats),
+        })
+    }
+}
+
+/// Result of a finished scan, folder entries are not sorted
+#[derive(Debug)]
+pub struct ScanTree<S: DataStore<DataStoreKey> = DataStoreType> {
+    pub root: PathBuf,
+    /// Record of every scanned folder, keyed by absolute path
+    pub store: S,
+    /// Totals and duration of the scan
+    pub stats: ScanStats,
+}
+
+impl<S: DataStore<DataStoreKey>> ScanTree<S> {
+    pub fn folder(&self, path: &Path) -> Option<&Folder> {
+        self.store.get_folder(&path.to_path_buf())
+    }
+
+    /// Total size of the root folder
+    pub fn size(&self) -> u64 {
+        self.folder(&self.root).map_or(0, Folder::get_size)
+    }
+
+    /// Paths which could not be read
+    pub fn errors(&self) -> &[ScanError] {
+        self.store.get_errors()
+    }
+}
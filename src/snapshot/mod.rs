@@ -0,0 +1,170 @@
+use crate::audit_log::format_timestamp;
+use crate::fs::{
+    DataStore, DataStoreKey, Folder, FolderEntry, FolderEntryType, SizeChange, SizeMode,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::File;
+use std::io::{self, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Entry of a snapshot, path is relative to its root
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SnapshotEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub is_folder: bool,
+}
+
+/// Sizes of every file and scanned folder of a completed scan
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Snapshot {
+    pub root: PathBuf,
+    /// RFC 3339 time in UTC
+    pub timestamp: String,
+    pub size_mode: SizeMode,
+    pub entries: Vec<SnapshotEntry>,
+}
+
+impl Snapshot {
+    /// Excluded folders and entries of unknown size are skipped
+    pub fn from_store<S: DataStore<DataStoreKey>>(
+        store: &S,
+        root: &Path,
+        size_mode: &SizeMode,
+    ) -> Self {
+        let mut entries = vec![];
+        collect_entries(store, root, Path::new(""), &mut entries);
+
+        Snapshot {
+            root: root.to_path_buf(),
+            timestamp: format_timestamp(SystemTime::now()),
+            size_mode: size_mode.clone(),
+            entries,
+        }
+    }
+
+    pub fn read(path: &Path) -> io::Result<Self> {
+        let file = File::open(path)?;
+        Ok(serde_json::from_reader(BufReader::new(file))?)
+    }
+
+    pub fn write(&self, writer: impl Write) -> io::Result<()> {
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    fn sizes(&self) -> HashMap<&Path, &SnapshotEntry> {
+        self.entries
+            .iter()
+            .map(|entry| (entry.path.as_path(), entry))
+            .collect()
+    }
+}
+
+fn collect_entries<S: DataStore<DataStoreKey>>(
+    store: &S,
+    path: &Path,
+    relative_path: &Path,
+    entries: &mut Vec<SnapshotEntry>,
+) {
+    let Some(folder) = store.get_folder(&path.to_path_buf()) else {
+        return;
+    };
+    let mut folder_entries: Vec<&FolderEntry> = folder.entries.iter().collect();
+    folder_entries.sort();
+    for entry in folder_entries {
+        let Some(size) = entry.size else {
+            continue;
+        };
+        let is_folder = match entry.kind {
+            FolderEntryType::Parent => continue,
+            FolderEntryType::Folder if entry.is_excluded => continue,
+            FolderEntryType::Folder => true,
+            FolderEntryType::File => false,
+        };
+        let entry_path = relative_path.join(&entry.title);
+        entries.push(SnapshotEntry {
+            path: entry_path.clone(),
+            size,
+            is_folder,
+        });
+        if is_folder {
+            collect_entries(store, &path.join(&entry.title), &entry_path, entries);
+        }
+    }
+}
+
+/// Fills store with entries of both snapshots, sizes are taken from the newer one.
+/// Returns root of the newer snapshot
+pub fn diff_snapshots<S: DataStore<DataStoreKey>>(
+    old: &Snapshot,
+    new: &Snapshot,
+    store: &mut S,
+) -> io::Result<PathBuf> {
+    if old.size_mode != new.size_mode {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "snapshots were taken in different size modes",
+        ));
+    }
+    let old_sizes = old.sizes();
+    let new_sizes = new.sizes();
+    let paths: BTreeSet<&Path> = old_sizes.keys().chain(new_sizes.keys()).copied().collect();
+
+    let root_title = new
+        .root
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| new.root.to_string_lossy().to_string());
+    let mut folders = BTreeMap::from([(PathBuf::new(), Folder::new(root_title))]);
+    for path in paths {
+        let old_entry = old_sizes.get(path);
+        let new_entry = new_sizes.get(path);
+        let Some(title) = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+        else {
+            continue;
+        };
+        let is_folder = new_entry.or(old_entry).is_some_and(|entry| entry.is_folder);
+        if is_folder {
+            folders
+                .entry(path.to_path_buf())
+                .or_insert_with(|| Folder::new(title.clone()));
+        }
+
+        let new_size = new_entry.map(|entry| entry.size);
+        let parent = path.parent().unwrap_or(Path::new("")).to_path_buf();
+        let parent_title = parent
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+        folders
+            .entry(parent)
+            .or_insert_with(|| Folder::new(parent_title))
+            .entries
+            .push(FolderEntry {
+                title,
+                size: new_size,
+                kind: match is_folder {
+                    true => FolderEntryType::Folder,
+                    false => FolderEntryType::File,
+                },
+                is_loaded: true,
+                change: Some(SizeChange::new(old_entry.map(|entry| entry.size), new_size)),
+                ..FolderEntry::default()
+            });
+    }
+
+    for (path, folder) in folders {
+        let folder_path = match path.as_os_str().is_empty() {
+            true => new.root.clone(),
+            false => new.root.join(path),
+        };
+        store.set_folder(&folder_path, folder);
+    }
+    Ok(new.root.clone())
+}
@@ -0,0 +1,93 @@
+use crate::fs::{DataStore, DataStoreKey, FolderEntryType};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::path::{Path, PathBuf};
+
+/// Amount of files listed in the largest files view
+pub const LARGEST_FILES_LIMIT: usize = 100;
+
+/// Largest files of the scanned tree, the smallest one is dropped once the limit is reached
+#[derive(Debug, Clone)]
+pub struct LargestFiles {
+    limit: usize,
+    heap: BinaryHeap<Reverse<(u64, PathBuf)>>,
+}
+
+impl LargestFiles {
+    pub fn new(limit: usize) -> Self {
+        LargestFiles {
+            limit,
+            heap: BinaryHeap::with_capacity(limit + 1),
+        }
+    }
+
+    /// Replaces size of a path which is already listed
+    pub fn push(&mut self, path: PathBuf, size: u64) {
+        self.heap.retain(|Reverse((_, listed))| *listed != path);
+        // Most files are smaller than every listed one
+        let is_full = self.heap.len() >= self.limit;
+        if is_full
+            && self
+                .heap
+                .peek()
+                .is_none_or(|Reverse((min, _))| size <= *min)
+        {
+            return;
+        }
+        self.heap.push(Reverse((size, path)));
+        if self.heap.len() > self.limit {
+            self.heap.pop();
+        }
+    }
+
+    /// Forgets provided path and every file inside it
+    pub fn remove(&mut self, path: &Path) {
+        self.heap
+            .retain(|Reverse((_, listed))| !listed.starts_with(path));
+    }
+
+    pub fn clear(&mut self) {
+        self.heap.clear();
+    }
+
+    pub fn len(&self) -> usize {
+        self.heap.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.heap.is_empty()
+    }
+
+    /// Collects files of a tree which was not scanned, e.g. imported from a dump
+    pub fn collect<S: DataStore<DataStoreKey>>(&mut self, store: &mut S) {
+        for folder_path in store.get_keys() {
+            let Some(folder) = store.get_folder(&folder_path) else {
+                continue;
+            };
+            for entry in folder.entries.iter() {
+                if let (FolderEntryType::File, Some(size)) = (&entry.kind, entry.size) {
+                    self.push(folder_path.join(&entry.title), size);
+                }
+            }
+        }
+    }
+
+    /// Largest first
+    pub fn to_sorted_vec(&self) -> Vec<(PathBuf, u64)> {
+        let mut files: Vec<(PathBuf, u64)> = self
+            .heap
+            .iter()
+            .map(|Reverse((size, path))| (path.clone(), *size))
+            .collect();
+        files.sort_by(|(path_a, size_a), (path_b, size_b)| {
+            size_b.cmp(size_a).then_with(|| path_a.cmp(path_b))
+        });
+        files
+    }
+}
+
+impl Default for LargestFiles {
+    fn default() -> Self {
+        Self::new(LARGEST_FILES_LIMIT)
+    }
+}
@@ -1,23 +1,73 @@
-use crate::fs::{path_to_folder, DataStore, DataStoreKey, Folder, FolderEntry, FolderEntryType};
-use crate::logger::Logger;
+use crate::fs::{
+    allocated_size, device_id, hard_link_id, path_to_folder, DataStore, DataStoreKey,
+    ExcludeFilter, Folder, FolderEntry, FolderEntryType, GitFilter, GitStatus, ScanError,
+    ScanOptions, SizeMode,
+};
 use crossbeam::channel::{Receiver, Sender};
+use std::collections::{hash_map, HashMap};
 use std::ffi::OsStr;
+use std::io;
 use std::marker::PhantomData;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Instant, SystemTime};
+
+mod largest;
+mod progress;
+pub use largest::{LargestFiles, LARGEST_FILES_LIMIT};
+pub use progress::{ScanProgress, ScanStats, PROGRESS_INTERVAL};
+
+/// Keeps the counter at zero instead of wrapping when a folder is reported as read twice
+fn decrement_pending(directories_pending: &AtomicU64) {
+    let _ = directories_pending.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |pending| {
+        Some(pending.saturating_sub(1))
+    });
+}
 
 #[derive(Debug)]
 pub struct EntryState {
-    size: u64,
+    apparent_size: u64,
+    allocated_size: u64,
+    /// Set for files with several hard links
+    hard_link_id: Option<(u64, u64)>,
+    /// Folder is located on another filesystem than its parent
+    is_mount_point: bool,
+    /// Folder matches exclude patterns and is not traversed
+    is_excluded: bool,
+    git_status: Option<GitStatus>,
+    /// Set for folders, compared with the cached one on next launch
+    modified: Option<SystemTime>,
+}
+
+impl EntryState {
+    pub fn size(&self, size_mode: &SizeMode) -> u64 {
+        match size_mode {
+            SizeMode::Apparent => self.apparent_size,
+            SizeMode::Allocated => self.allocated_size,
+        }
+    }
+}
+
+/// Passed down to subfolders, extended with their ignore files
+#[derive(Debug, Clone, Default)]
+pub struct ReadDirState {
+    exclude: ExcludeFilter,
+    /// Set in git mode
+    git: Option<GitFilter>,
 }
 
-type WalkDir = jwalk::WalkDirGeneric<((), Option<Result<EntryState, jwalk::Error>>)>;
+type WalkDir = jwalk::WalkDirGeneric<(ReadDirState, Option<Result<EntryState, jwalk::Error>>)>;
 
 pub type TraversalEntry =
-    Result<jwalk::DirEntry<((), Option<Result<EntryState, jwalk::Error>>)>, jwalk::Error>;
+    Result<jwalk::DirEntry<(ReadDirState, Option<Result<EntryState, jwalk::Error>>)>, jwalk::Error>;
 
+/// Events are tagged with generation of the scan which produced them
 #[derive(Debug)]
 pub enum TraversalEvent {
-    Entry(TraversalEntry),
+    Entry(u64, TraversalEntry),
+    /// Counters of a single walk, sent periodically and before it finishes
+    Progress(u64, usize, ScanProgress),
     Finished(u64),
 }
 
@@ -26,49 +76,142 @@ pub struct TaskManager<S: DataStore<DataStoreKey>> {
     pub event_tx: Sender<TraversalEvent>,
     pub event_rx: Receiver<TraversalEvent>,
     pub is_working: bool,
+    /// Amount of walks of current generation which are not finished yet
+    pub active_walks: usize,
+    /// Incremented on reset, events of previous generations are dropped
+    pub generation: u64,
+    /// Stops walks of current generation when set
+    cancel_token: Arc<AtomicBool>,
+    pub options: ScanOptions,
+    /// Live counters of current generation
+    pub stats: ScanStats,
+    /// Hard-linked files which are already counted, with the path they are counted at
+    pub seen_hard_links: HashMap<(u64, u64), PathBuf>,
+    /// Largest files found by current generation
+    pub largest_files: LargestFiles,
     _store: PhantomData<S>,
 }
 
 impl<S: DataStore<DataStoreKey>> TaskManager<S> {
-    pub fn new() -> Self {
+    pub fn new(options: ScanOptions) -> Self {
         let (entry_tx, entry_rx) = crossbeam::channel::bounded(100);
         Self {
             event_rx: entry_rx,
             event_tx: entry_tx,
             is_working: false,
+            active_walks: 0,
+            generation: 0,
+            cancel_token: Arc::new(AtomicBool::new(false)),
+            options,
+            stats: ScanStats::default(),
+            seen_hard_links: HashMap::new(),
+            largest_files: LargestFiles::default(),
             _store: PhantomData,
         }
     }
 
+    /// Cancels running traversals and forgets state collected from them
+    pub fn reset(&mut self) {
+        self.cancel_token.store(true, Ordering::Relaxed);
+        self.cancel_token = Arc::new(AtomicBool::new(false));
+        self.generation += 1;
+        self.active_walks = 0;
+        self.is_working = false;
+        self.stats.reset();
+        self.seen_hard_links.clear();
+        self.largest_files.clear();
+    }
+
+    /// Forgets hard links counted inside provided path, so they are counted again when it is rescanned
+    pub fn forget_hard_links(&mut self, path: &Path) {
+        self.seen_hard_links
+            .retain(|_, counted_at| !counted_at.starts_with(path));
+    }
+
     pub fn is_done(&self) -> bool {
         !self.is_working
     }
 
-    pub fn start(&mut self, input: Vec<DataStoreKey>, logger: &mut Logger) {
-        logger.start_timer("Traversal");
+    /// Fails when the traversal thread can not be spawned, nothing is scanned then
+    pub fn start(&mut self, input: Vec<DataStoreKey>) -> io::Result<()> {
         self.is_working = true;
+        self.active_walks += 1;
         let entry_tx = self.event_tx.clone();
-        let _ = std::thread::Builder::new()
+        let options = self.options.clone();
+        let generation = self.generation;
+        let cancel_token = self.cancel_token.clone();
+        let walk = self.stats.start_walk();
+        let spawned = std::thread::Builder::new()
             .name("wiper-walk-dispatcher".to_string())
             .spawn({
                 move || {
+                    let mut progress = ScanProgress::default();
+                    let directories_pending = Arc::new(AtomicU64::new(0));
+                    let mut reported_at = Instant::now();
+
                     for root_path in input.into_iter() {
-                        for entry in Self::iter_from_path(&root_path).into_iter() {
-                            if entry_tx.send(TraversalEvent::Entry(entry)).is_err() {
+                        let walk_dir =
+                            Self::iter_from_path(&root_path, &options, directories_pending.clone());
+                        for entry in walk_dir.into_iter() {
+                            // Dropping the iterator stops the walk
+                            if cancel_token.load(Ordering::Relaxed) {
+                                return;
+                            }
+                            progress.record(&entry, &options.size_mode);
+                            // Folder which failed to be read is not pending anymore
+                            if matches!(&entry, Ok(e) if e.read_children_error.is_some()) {
+                                decrement_pending(&directories_pending);
+                            }
+                            if entry_tx
+                                .send(TraversalEvent::Entry(generation, entry))
+                                .is_err()
+                            {
                                 println!("Send err: channel closed");
                                 return;
                             }
+                            if reported_at.elapsed() >= PROGRESS_INTERVAL {
+                                progress.directories_pending =
+                                    directories_pending.load(Ordering::Relaxed);
+                                let event =
+                                    TraversalEvent::Progress(generation, walk, progress.clone());
+                                if entry_tx.send(event).is_err() {
+                                    return;
+                                }
+                                reported_at = Instant::now();
+                            }
                         }
                     }
-                    let _ = entry_tx.send(TraversalEvent::Finished(0));
+                    progress.directories_pending = 0;
+                    let _ = entry_tx.send(TraversalEvent::Progress(generation, walk, progress));
+                    let _ = entry_tx.send(TraversalEvent::Finished(generation));
                 }
             });
+        if let Err(err) = spawned {
+            self.finish_walk();
+            return Err(err);
+        }
+        Ok(())
     }
 
-    pub fn process_results(&mut self, store: &mut S, logger: &mut Logger) {
+    fn finish_walk(&mut self) {
+        self.active_walks = self.active_walks.saturating_sub(1);
+        if self.active_walks == 0 {
+            self.is_working = false;
+            self.stats.finish();
+        }
+    }
+
+    pub fn process_results(&mut self, store: &mut S) {
         while let Ok(event) = self.event_rx.try_recv() {
             match event {
-                TraversalEvent::Entry(entry) => match entry {
+                TraversalEvent::Entry(generation, _)
+                | TraversalEvent::Progress(generation, _, _)
+                | TraversalEvent::Finished(generation)
+                    if generation != self.generation =>
+                {
+                    // Stale event from cancelled scan
+                }
+                TraversalEvent::Entry(_, entry) => match entry {
                     Ok(e) => {
                         // Construct entry
                         let belongs_to = e.parent_path.to_path_buf();
@@ -80,34 +223,89 @@ impl<S: DataStore<DataStoreKey>> TaskManager<S> {
                             .unwrap_or("")
                             .to_string();
 
+                        // Folder content could not be read
+                        let read_error = e
+                            .read_children_error
+                            .as_ref()
+                            .map(|err| ScanError::from_walk_error(err, &e.path()));
+
                         let kind = match e.file_type().is_dir() {
                             true => {
                                 // Create store record for folder (edge-case for last-leaf-empty
                                 // folders)
-                                let default_folder = Folder::new(title.clone());
+                                let mut default_folder = Folder::new(title.clone());
+                                default_folder.has_error = read_error.is_some();
+                                default_folder.modified = match e.client_state.as_ref() {
+                                    Some(Ok(my_entry)) => my_entry.modified,
+                                    _ => None,
+                                };
                                 store.set_folder(&e.path().clone(), default_folder);
 
                                 FolderEntryType::Folder
                             }
                             false => FolderEntryType::File,
                         };
+                        let (is_mount_point, is_excluded, git_status) =
+                            match e.client_state.as_ref() {
+                                Some(Ok(my_entry)) => (
+                                    my_entry.is_mount_point,
+                                    my_entry.is_excluded,
+                                    my_entry.git_status,
+                                ),
+                                _ => (false, false, None),
+                            };
+                        let entry_error = match e.client_state.as_ref() {
+                            Some(Err(err)) => Some(ScanError::from_walk_error(err, &e.path())),
+                            _ => None,
+                        }
+                        .or(read_error);
+                        let mut is_duplicate_link = false;
                         let size = match e.client_state.as_ref() {
                             Some(Ok(my_entry)) => {
                                 if kind == FolderEntryType::Folder {
                                     // Ignore folder metadata size
                                     0
+                                } else if let Some(id) = my_entry.hard_link_id {
+                                    // Count every hard-linked inode only once
+                                    if let hash_map::Entry::Vacant(seen) =
+                                        self.seen_hard_links.entry(id)
+                                    {
+                                        seen.insert(e.path());
+                                        my_entry.size(&self.options.size_mode)
+                                    } else {
+                                        is_duplicate_link = true;
+                                        0
+                                    }
                                 } else {
-                                    my_entry.size
+                                    my_entry.size(&self.options.size_mode)
                                 }
                             }
                             _ => 0,
                         };
+                        let ignored_size = match git_status {
+                            Some(GitStatus::Ignored) => size,
+                            _ => 0,
+                        };
+
+                        if kind == FolderEntryType::File && !is_duplicate_link {
+                            self.largest_files.push(e.path(), size);
+                        }
 
                         let folder_entry = FolderEntry {
                             title: title.clone(),
-                            size: Some(size),
+                            // Excluded folders have no known size
+                            size: if is_excluded { None } else { Some(size) },
                             is_loaded: true,
                             kind,
+                            is_mount_point,
+                            is_excluded,
+                            git_status,
+                            ignored_size,
+                            has_error: entry_error.is_some(),
+                            is_marked: false,
+                            is_protected: self.options.protect.is_protected(&e.path()),
+                            change: None,
+                            is_stale: false,
                         };
 
                         // Add entry to parent folder
@@ -117,18 +315,28 @@ impl<S: DataStore<DataStoreKey>> TaskManager<S> {
                                 if !extension.is_empty() {
                                     folder.append_file_type_size(&extension, size);
                                 }
+                                if is_duplicate_link {
+                                    folder.hard_links += 1;
+                                }
                                 folder.entries.push(folder_entry);
                             }
                             None => {
                                 if let Some(belongs_to_name) = belongs_to.file_name() {
                                     let mut folder =
                                         Folder::new(belongs_to_name.to_string_lossy().to_string());
+                                    if is_duplicate_link {
+                                        folder.hard_links += 1;
+                                    }
                                     folder.entries.push(folder_entry);
                                     store.set_folder(&belongs_to.to_path_buf(), folder);
                                 }
                             }
                         };
 
+                        if let Some(error) = entry_error {
+                            store.add_error(error);
+                        }
+
                         // Traverse tree up - update parent folder sizes
                         if let Some(title_traverse_os) = belongs_to.file_name() {
                             let mut title_traverse =
@@ -148,6 +356,7 @@ impl<S: DataStore<DataStoreKey>> TaskManager<S> {
                                             && child.kind == FolderEntryType::Folder
                                         {
                                             child.increment_size(size);
+                                            child.ignored_size += ignored_size;
                                             parent_folder.sorted_by = None;
                                             break;
                                         }
@@ -156,6 +365,9 @@ impl<S: DataStore<DataStoreKey>> TaskManager<S> {
                                     if !extension.is_empty() {
                                         parent_folder.append_file_type_size(&extension, size);
                                     }
+                                    if is_duplicate_link {
+                                        parent_folder.hard_links += 1;
+                                    }
                                     title_traverse.clone_from(&parent_folder.title);
                                     path_traverse = parent_buf.to_path_buf();
                                 } else {
@@ -164,36 +376,89 @@ impl<S: DataStore<DataStoreKey>> TaskManager<S> {
                             }
                         }
                     }
-                    Err(_) => {
-                        logger.log("Done".into());
+                    Err(err) => {
+                        let fallback_path = store.get_current_path().clone();
+                        let error = ScanError::from_walk_error(&err, &fallback_path);
+                        // Folder which could not be read
+                        if let Some(folder) = store.get_folder_mut(&error.path) {
+                            folder.has_error = true;
+                        }
+                        Self::record_error(store, error);
                     }
                 },
-                TraversalEvent::Finished(_) => {
-                    self.is_working = false;
-                    logger.stop_timer("Traversal");
+                TraversalEvent::Progress(_, walk, progress) => {
+                    self.stats.update(walk, progress);
+                }
+                TraversalEvent::Finished(_) => self.finish_walk(),
+            }
+        }
+    }
+
+    /// Stores error and marks entry of its path
+    fn record_error(store: &mut S, error: ScanError) {
+        if let (Some(parent), Some(title)) = (error.path.parent(), error.path.file_name()) {
+            if let Some(folder) = store.get_folder_mut(&parent.to_path_buf()) {
+                let title = title.to_string_lossy();
+                if let Some(entry) = folder.entries.iter_mut().find(|e| e.title == title) {
+                    entry.has_error = true;
                 }
             }
         }
+        store.add_error(error);
+    }
+
+    /// Folder which is kept without traversing it
+    fn is_skipped(&self, entry: &FolderEntry) -> bool {
+        entry.is_excluded || (self.options.one_file_system && entry.is_mount_point)
     }
 
-    pub fn process_path_sync(&self, store: &mut S, path: &DataStoreKey) -> Vec<DataStoreKey> {
-        let mut folder_new = path_to_folder(path.clone());
+    /// Counts a file which is read synchronously only once, like the walker does.
+    /// Returns `true` when it is a hard link to a file which is already counted
+    fn is_duplicate_link(&mut self, file_path: PathBuf, entry: &mut FolderEntry) -> bool {
+        let metadata = file_path.symlink_metadata().ok();
+        let Some(id) = metadata.as_ref().and_then(hard_link_id) else {
+            return false;
+        };
+        match self.seen_hard_links.entry(id) {
+            hash_map::Entry::Vacant(seen) => {
+                seen.insert(file_path);
+                false
+            }
+            hash_map::Entry::Occupied(_) => {
+                entry.size = Some(0);
+                entry.ignored_size = 0;
+                true
+            }
+        }
+    }
+
+    pub fn process_path_sync(&mut self, store: &mut S, path: &DataStoreKey) -> Vec<DataStoreKey> {
+        let (mut folder_new, errors) = path_to_folder(path.clone(), &self.options);
+        for error in errors {
+            store.add_error(error);
+        }
         let mut paths_to_process: Vec<DataStoreKey> = vec![];
         let mut entries_to_keep: Vec<FolderEntry> = vec![];
 
         match store.get_folder_mut(path) {
             Some(folder_stored) => {
                 // Folder already exists
+                folder_stored.has_error = folder_new.has_error;
                 for child in folder_new.entries.iter_mut() {
                     if !folder_stored.entries.iter().any(|e| e.title == child.title) {
                         // No entry
-                        if child.kind == FolderEntryType::Folder {
+                        if child.kind == FolderEntryType::Folder && !self.is_skipped(child) {
                             // Folder -> process
                             let mut child_path = path.clone();
                             child_path.push(child.title.clone());
                             paths_to_process.push(child_path);
                         } else {
-                            // File -> simply push
+                            // File, excluded folder or other filesystem -> simply push
+                            if child.kind == FolderEntryType::File
+                                && self.is_duplicate_link(path.join(&child.title), child)
+                            {
+                                folder_stored.hard_links += 1;
+                            }
                             folder_stored.entries.push(child.clone());
                         }
                     }
@@ -202,13 +467,18 @@ impl<S: DataStore<DataStoreKey>> TaskManager<S> {
             None => {
                 // Folder does not exist
                 for child in folder_new.entries.iter_mut() {
-                    if child.kind == FolderEntryType::Folder {
+                    if child.kind == FolderEntryType::Folder && !self.is_skipped(child) {
                         // Folder -> process
                         let mut child_path = path.clone();
                         child_path.push(child.title.clone());
                         paths_to_process.push(child_path);
                     } else {
-                        // File -> simply push
+                        // File, excluded folder or other filesystem -> simply push
+                        if child.kind == FolderEntryType::File
+                            && self.is_duplicate_link(path.join(&child.title), child)
+                        {
+                            folder_new.hard_links += 1;
+                        }
                         entries_to_keep.push(child.clone());
                     }
                 }
@@ -220,31 +490,93 @@ impl<S: DataStore<DataStoreKey>> TaskManager<S> {
         paths_to_process
     }
 
-    pub fn iter_from_path(root_path: &PathBuf) -> WalkDir {
+    /// `directories_pending` counts folders which are found but not read yet
+    pub fn iter_from_path(
+        root_path: &PathBuf,
+        options: &ScanOptions,
+        directories_pending: Arc<AtomicU64>,
+    ) -> WalkDir {
         let threads = num_cpus::get();
 
-        let ignore_dirs = [];
+        let one_file_system = options.one_file_system;
+        let root_device = root_path
+            .metadata()
+            .ok()
+            .and_then(|metadata| device_id(&metadata));
 
         WalkDir::new(root_path)
             .follow_links(false)
             .skip_hidden(false)
+            .root_read_dir_state(ReadDirState {
+                exclude: options.exclude.for_path(root_path),
+                git: options.git.then(|| GitFilter::for_path(root_path)),
+            })
             .process_read_dir({
-                move |_, _, _, dir_entry_results| {
+                move |depth, path, state, dir_entry_results| {
+                    let parent_device = path
+                        .metadata()
+                        .ok()
+                        .and_then(|metadata| device_id(&metadata));
+
+                    // Root entry is processed with path of its parent and is never excluded
+                    let is_root = depth.is_none();
+                    if !is_root {
+                        decrement_pending(&directories_pending);
+                        state.exclude = state.exclude.with_ignore_file(path);
+                        if let Some(git) = state.git.as_mut() {
+                            *git = git.enter(path);
+                        }
+                        dir_entry_results.retain(|dir_entry_result| match dir_entry_result {
+                            Ok(dir_entry) => {
+                                dir_entry.file_type().is_dir()
+                                    || !state.exclude.is_excluded(&dir_entry.path())
+                            }
+                            Err(_) => true,
+                        });
+                    }
+
                     dir_entry_results.iter_mut().for_each(|dir_entry_result| {
                         if let Ok(dir_entry) = dir_entry_result {
                             let metadata = dir_entry.metadata();
+                            let is_excluded =
+                                !is_root && state.exclude.is_excluded(&dir_entry.path());
+                            let git_status = state.git.as_ref().and_then(|git| {
+                                git.status(&dir_entry.path(), dir_entry.file_type().is_dir())
+                            });
 
                             if let Ok(metadata) = metadata {
+                                let device = device_id(&metadata);
+                                let is_mount_point = metadata.is_dir()
+                                    && parent_device.is_some()
+                                    && device != parent_device;
+
+                                // Stay on the filesystem of the traversal root
+                                if one_file_system && metadata.is_dir() && device != root_device {
+                                    dir_entry.read_children_path = None;
+                                }
+
                                 dir_entry.client_state = Some(Ok(EntryState {
-                                    size: metadata.len(),
+                                    apparent_size: metadata.len(),
+                                    allocated_size: allocated_size(&metadata),
+                                    hard_link_id: hard_link_id(&metadata),
+                                    is_mount_point,
+                                    is_excluded,
+                                    git_status,
+                                    modified: metadata
+                                        .modified()
+                                        .ok()
+                                        .filter(|_| metadata.is_dir()),
                                 }));
                             } else {
                                 dir_entry.client_state = Some(Err(metadata.unwrap_err()));
                             }
 
-                            if ignore_dirs.contains(&dir_entry.path()) {
+                            if is_excluded {
                                 dir_entry.read_children_path = None;
                             }
+                            if dir_entry.read_children_path.is_some() {
+                                directories_pending.fetch_add(1, Ordering::Relaxed);
+                            }
                         }
                     })
                 }
@@ -270,6 +602,6 @@ impl<S: DataStore<DataStoreKey>> TaskManager<S> {
 
 impl<S: DataStore<DataStoreKey>> Default for TaskManager<S> {
     fn default() -> Self {
-        Self::new()
+        Self::new(ScanOptions::default())
     }
 }
@@ -0,0 +1,137 @@
+use crate::fs::SizeMode;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use super::TraversalEntry;
+
+/// Minimal time between progress events of a single walk
+pub const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Counters of a single walk, sent periodically with [`super::TraversalEvent::Progress`]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScanProgress {
+    pub files: u64,
+    pub directories: u64,
+    pub bytes: u64,
+    /// Folders which are found but not read yet
+    pub directories_pending: u64,
+    pub errors: u64,
+}
+
+impl ScanProgress {
+    pub fn entries(&self) -> u64 {
+        self.files + self.directories
+    }
+
+    pub fn record(&mut self, entry: &TraversalEntry, size_mode: &SizeMode) {
+        match entry {
+            Ok(e) if e.file_type().is_dir() => {
+                self.directories += 1;
+                if e.read_children_error.is_some() {
+                    self.errors += 1;
+                }
+            }
+            Ok(e) => {
+                self.files += 1;
+                match e.client_state.as_ref() {
+                    Some(Ok(state)) => self.bytes += state.size(size_mode),
+                    Some(Err(_)) => self.errors += 1,
+                    None => {}
+                }
+            }
+            Err(_) => self.errors += 1,
+        }
+    }
+
+    fn add(mut self, other: &ScanProgress) -> ScanProgress {
+        self.files += other.files;
+        self.directories += other.directories;
+        self.bytes += other.bytes;
+        self.directories_pending += other.directories_pending;
+        self.errors += other.errors;
+        self
+    }
+}
+
+/// Progress of all walks of current generation
+#[derive(Debug, Default)]
+pub struct ScanStats {
+    walks: HashMap<usize, ScanProgress>,
+    next_walk: usize,
+    started_at: Option<Instant>,
+    finished_at: Option<Instant>,
+    /// Totals of the last finished scan, used to estimate completion of a rescan
+    previous: Option<ScanProgress>,
+}
+
+impl ScanStats {
+    /// Registers new walk and returns its id
+    pub fn start_walk(&mut self) -> usize {
+        if self.started_at.is_none() || self.finished_at.is_some() {
+            // Walks of a finished scan are not counted in the new one
+            self.walks.clear();
+            self.started_at = Some(Instant::now());
+        }
+        self.finished_at = None;
+        self.next_walk += 1;
+        self.walks.insert(self.next_walk, ScanProgress::default());
+        self.next_walk
+    }
+
+    pub fn update(&mut self, walk: usize, progress: ScanProgress) {
+        self.walks.insert(walk, progress);
+    }
+
+    pub fn finish(&mut self) {
+        self.finished_at = Some(Instant::now());
+    }
+
+    /// Forgets walks, keeping totals of a finished scan for estimations
+    pub fn reset(&mut self) {
+        let total = self.total();
+        if self.finished_at.is_some() && total.entries() > 0 {
+            self.previous = Some(total);
+        }
+        self.walks.clear();
+        self.started_at = None;
+        self.finished_at = None;
+    }
+
+    pub fn total(&self) -> ScanProgress {
+        self.walks
+            .values()
+            .fold(ScanProgress::default(), |acc, progress| acc.add(progress))
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        match (self.started_at, self.finished_at) {
+            (Some(started_at), Some(finished_at)) => finished_at.duration_since(started_at),
+            (Some(started_at), None) => started_at.elapsed(),
+            _ => Duration::ZERO,
+        }
+    }
+
+    pub fn per_second(&self, value: u64) -> f64 {
+        let elapsed = self.elapsed().as_secs_f64();
+        match elapsed > 0.0 {
+            true => value as f64 / elapsed,
+            false => 0.0,
+        }
+    }
+
+    /// Share of entries seen compared to the previous scan
+    pub fn completion(&self) -> Option<f64> {
+        let previous = self.previous.as_ref()?.entries();
+        Some((self.total().entries() as f64 / previous as f64).min(1.0))
+    }
+
+    /// Remaining time of a rescan, based on current throughput
+    pub fn estimated_remaining(&self) -> Option<Duration> {
+        let completion = self.completion()?;
+        if completion <= 0.0 || self.finished_at.is_some() {
+            return None;
+        }
+        let elapsed = self.elapsed().as_secs_f64();
+        Some(Duration::from_secs_f64(elapsed / completion - elapsed))
+    }
+}
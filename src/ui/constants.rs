@@ -7,9 +7,14 @@ pub const TABLE_HEADER_BG: Color = tailwind::SLATE.c900;
 pub const TEXT_SELECTED_BG: Color = tailwind::SLATE.c700;
 pub const TEXT_PRE_DELETED_BG: Color = tailwind::RED.c600;
 pub const TEXT_HIGHLIGHTED: Color = tailwind::YELLOW.c400;
+pub const TEXT_MARKER: Color = tailwind::SLATE.c500;
+pub const TEXT_ERROR: Color = tailwind::RED.c500;
+pub const TEXT_SHRUNK: Color = tailwind::GREEN.c500;
 pub const TABLE_ICON_WIDTH: u16 = 2;
 pub const TABLE_NAME_WIDTH: u16 = 40;
 pub const TABLE_SIZE_WIDTH: u16 = 20;
+pub const TABLE_IGNORED_WIDTH: u16 = 12;
+pub const TABLE_DELTA_WIDTH: u16 = 14;
 pub const TABLE_SPACE_WIDTH: usize = 40;
 
 // Texts
@@ -17,5 +22,15 @@ pub const TEXT_UNKNOWN: &str = "N/A";
 pub const TEXT_PARENT_DIR: &str = "..";
 pub const TEXT_TITLE: &str = "Wiper";
 pub const TEXT_HINT_NAVIGATE: &str = "←↓↑→/Enter/Backspace - navigate";
+pub const TEXT_HINT_MARK: &str = "Space/a/i - mark";
 pub const TEXT_ICON_FOLDER: &str = "";
 pub const TEXT_ICON_FOLDER_ASCII: &str = "[]";
+pub const TEXT_ICON_ERROR: &str = "⚠";
+pub const TEXT_ICON_LOCK: &str = "🔒";
+pub const TEXT_MARKER_MOUNT_POINT: &str = "mount point";
+pub const TEXT_MARKER_EXCLUDED: &str = "excluded";
+pub const TEXT_MARKER_IGNORED: &str = "ignored";
+pub const TEXT_MARKER_UNTRACKED: &str = "untracked";
+pub const TEXT_MARKER_ADDED: &str = "added";
+pub const TEXT_MARKER_REMOVED: &str = "removed";
+pub const TEXT_MARKER_STALE: &str = "stale";
@@ -1,15 +1,20 @@
 use crate::config::UIConfig;
 use crate::fs::Folder;
+use crate::fs::ScanError;
 use crate::fs::SortBy;
 use crate::logger::Logger;
 use crate::logger::MessageLevel;
 use ratatui::{prelude::*, widgets::*};
+use std::path::PathBuf;
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::ui::constants::{
-    NORMAL_ROW_COLOR, TABLE_HEADER_BG, TABLE_HEADER_FG, TABLE_ICON_WIDTH, TABLE_NAME_WIDTH,
-    TABLE_SIZE_WIDTH, TABLE_SPACE_WIDTH, TEXT_COLOR, TEXT_PRE_DELETED_BG, TEXT_SELECTED_BG,
+    NORMAL_ROW_COLOR, TABLE_DELTA_WIDTH, TABLE_HEADER_BG, TABLE_HEADER_FG, TABLE_ICON_WIDTH,
+    TABLE_IGNORED_WIDTH, TABLE_NAME_WIDTH, TABLE_SIZE_WIDTH, TABLE_SPACE_WIDTH, TEXT_COLOR,
+    TEXT_PRE_DELETED_BG, TEXT_SELECTED_BG,
 };
+use crate::ui::errors::render_error_list;
+use crate::ui::largest::render_largest_files;
 use crate::ui::utils::folder_to_rows;
 
 const MAX_LOG_LEN: usize = 180;
@@ -21,10 +26,20 @@ pub struct DebugData {
     pub spin_symbol: (char, char),
 }
 
+/// List shown in the content area
+#[derive(Debug)]
+pub enum ContentView<'a> {
+    Folder(Option<&'a Folder>),
+    /// Errors with selected row
+    Errors(&'a [ScanError], usize),
+    /// Files with their sizes and selected row
+    LargestFiles(&'a [(PathBuf, u64)], usize),
+}
+
 pub fn render_content(
     area: Rect,
     buf: &mut Buffer,
-    maybe_folder: Option<&Folder>,
+    view: ContentView,
     config: &UIConfig,
     logger: &Logger,
     debug_data: &DebugData,
@@ -36,8 +51,13 @@ pub fn render_content(
 
     let [content_col, debug_col] = horizontal_layout.areas(area);
 
-    if let Some(folder) = maybe_folder {
-        render_table(content_col, buf, folder, config);
+    match view {
+        ContentView::Errors(errors, cursor) => render_error_list(content_col, buf, errors, cursor),
+        ContentView::LargestFiles(files, cursor) => {
+            render_largest_files(content_col, buf, files, cursor, config)
+        }
+        ContentView::Folder(Some(folder)) => render_table(content_col, buf, folder, config),
+        ContentView::Folder(None) => {}
     }
 
     if config.debug_enabled {
@@ -53,26 +73,69 @@ pub fn render_table(area: Rect, buf: &mut Buffer, folder: &Folder, config: &UICo
         .fg(TEXT_COLOR)
         .bg(NORMAL_ROW_COLOR);
 
+    // Ignored sizes column is only shown in git mode
+    let ignored_width = match config.git {
+        true => TABLE_IGNORED_WIDTH,
+        false => 0,
+    };
+    // Size delta column is only shown when comparing snapshots
+    let delta_width = match config.diff {
+        true => TABLE_DELTA_WIDTH,
+        false => 0,
+    };
+
     let layout = Layout::horizontal([
         Constraint::Fill(1),
         Constraint::Length(
-            TABLE_ICON_WIDTH + TABLE_NAME_WIDTH + TABLE_SIZE_WIDTH + TABLE_SPACE_WIDTH as u16 + 4,
+            TABLE_ICON_WIDTH
+                + TABLE_NAME_WIDTH
+                + TABLE_SIZE_WIDTH
+                + ignored_width
+                + delta_width
+                + TABLE_SPACE_WIDTH as u16
+                + 4,
         ),
         Constraint::Fill(1),
     ]);
     let [_, col_table, _] = layout.areas(area);
 
     let header_style = Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG);
-    let selected_style = if config.confirming_deletion {
+    // Marked entries are highlighted instead when deleting in batch
+    let selected_style = if config.confirming_deletion
+        && !config.confirming_permanent
+        && folder.get_marked_entries().is_empty()
+    {
         Style::default().bg(TEXT_PRE_DELETED_BG)
     } else {
         Style::default().bg(TEXT_SELECTED_BG)
     };
 
-    let header_titles = match config.sort_by {
-        SortBy::Title => ["", "Name ↓", "Size", "Space"],
-        SortBy::Size => ["", "Name", "Size ↓", "Space"],
+    let mut header_titles = match config.sort_by {
+        SortBy::Title => vec!["", "Name ↓", "Size", "Space"],
+        SortBy::Size => vec!["", "Name", "Size ↓", "Space"],
+        SortBy::Delta => vec!["", "Name", "Size", "Space"],
     };
+    let mut widths = vec![
+        Constraint::Length(TABLE_ICON_WIDTH),
+        Constraint::Length(TABLE_NAME_WIDTH),
+        Constraint::Length(TABLE_SIZE_WIDTH),
+        Constraint::Length(TABLE_SPACE_WIDTH as u16),
+    ];
+    // Optional columns go before the space bar, in the same order as row cells
+    if config.git {
+        header_titles.insert(header_titles.len() - 1, "Ignored");
+        widths.insert(widths.len() - 1, Constraint::Length(TABLE_IGNORED_WIDTH));
+    }
+    if config.diff {
+        header_titles.insert(
+            header_titles.len() - 1,
+            match config.sort_by {
+                SortBy::Delta => "Delta ↓",
+                _ => "Delta",
+            },
+        );
+        widths.insert(widths.len() - 1, Constraint::Length(TABLE_DELTA_WIDTH));
+    }
 
     let header = header_titles
         .into_iter()
@@ -83,20 +146,12 @@ pub fn render_table(area: Rect, buf: &mut Buffer, folder: &Folder, config: &UICo
 
     let rows = folder_to_rows(folder, config);
 
-    let table = Table::new(
-        rows,
-        [
-            Constraint::Length(TABLE_ICON_WIDTH),
-            Constraint::Length(TABLE_NAME_WIDTH),
-            Constraint::Length(TABLE_SIZE_WIDTH),
-            Constraint::Length(TABLE_SPACE_WIDTH as u16),
-        ],
-    )
-    .block(block)
-    .header(header)
-    .highlight_symbol("> ")
-    .highlight_style(selected_style)
-    .highlight_spacing(HighlightSpacing::Always);
+    let table = Table::new(rows, widths)
+        .block(block)
+        .header(header)
+        .highlight_symbol("> ")
+        .highlight_style(selected_style)
+        .highlight_spacing(HighlightSpacing::Always);
 
     StatefulWidget::render(
         table,
@@ -0,0 +1,52 @@
+use crate::fs::ScanError;
+use ratatui::{prelude::*, widgets::*};
+
+use crate::ui::constants::{
+    NORMAL_ROW_COLOR, TABLE_HEADER_BG, TABLE_HEADER_FG, TEXT_COLOR, TEXT_ERROR, TEXT_SELECTED_BG,
+};
+
+pub fn render_error_list(area: Rect, buf: &mut Buffer, errors: &[ScanError], cursor: usize) {
+    let block = Block::default()
+        .title(format!(" Errors: {} ", errors.len()))
+        .padding(Padding::horizontal(1))
+        .borders(Borders::ALL)
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+
+    let header = ["Path", "Kind", "Message"]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG))
+        .height(1);
+
+    let rows = errors.iter().map(|error| {
+        Row::new(vec![
+            Text::from(error.path.to_string_lossy().to_string()),
+            Text::from(format!("{:?}", error.kind)).style(Style::default().fg(TEXT_ERROR)),
+            Text::from(error.message.clone()),
+        ])
+    });
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Fill(3),
+            Constraint::Length(20),
+            Constraint::Fill(2),
+        ],
+    )
+    .block(block)
+    .header(header)
+    .highlight_symbol("> ")
+    .highlight_style(Style::default().bg(TEXT_SELECTED_BG))
+    .highlight_spacing(HighlightSpacing::Always);
+
+    StatefulWidget::render(
+        table,
+        area,
+        buf,
+        &mut TableState::default().with_selected(Some(cursor)),
+    );
+}
@@ -1,4 +1,4 @@
-use crate::ui::constants::TEXT_HINT_NAVIGATE;
+use crate::ui::constants::{TEXT_HINT_MARK, TEXT_HINT_NAVIGATE};
 use ratatui::{prelude::*, widgets::*};
 
 use super::utils::color_capital_letter;
@@ -8,6 +8,9 @@ pub fn render_footer(area: Rect, buf: &mut Buffer) {
     let inner_area = block.inner(area);
     Widget::render(block, area, buf);
 
+    let [row_main, row_extra] =
+        Layout::vertical([Constraint::Length(1), Constraint::Length(1)]).areas(inner_area);
+
     let layout = Layout::horizontal([
         Constraint::Fill(1),
         Constraint::Max(13),
@@ -18,7 +21,7 @@ pub fn render_footer(area: Rect, buf: &mut Buffer) {
         Constraint::Max(4),
     ]);
     let [col_navigate, col_version, col_explore, col_refresh, col_sort, col_delete, col_quit] =
-        layout.areas(inner_area);
+        layout.areas(row_main);
 
     let version = env!("CARGO_PKG_VERSION");
     let text_version = format!("v:{version}");
@@ -49,4 +52,42 @@ pub fn render_footer(area: Rect, buf: &mut Buffer) {
     Paragraph::new(text_quit)
         .left_aligned()
         .render(col_quit, buf);
+
+    // Shifted keys are prefixed, their letters are shared with lowercase ones
+    let layout = Layout::horizontal([
+        Constraint::Fill(1),
+        Constraint::Max(10),
+        Constraint::Max(17),
+        Constraint::Max(9),
+        Constraint::Max(6),
+        Constraint::Max(16),
+    ]);
+    let [col_mark, col_largest, col_refresh_current, col_errors, col_undo, col_permanent] =
+        layout.areas(row_extra);
+
+    let text_largest = color_capital_letter("Largest,".into(), Some("⇧".into()), None);
+    let text_refresh_current =
+        color_capital_letter("Refresh folder,".into(), Some("⇧".into()), None);
+    let text_errors = color_capital_letter("Errors,".into(), Some("⇧".into()), None);
+    let text_undo = color_capital_letter("Undo,".into(), None, None);
+    let text_permanent = color_capital_letter("Permanently - 2x".into(), None, None);
+
+    Paragraph::new(TEXT_HINT_MARK)
+        .left_aligned()
+        .render(col_mark, buf);
+    Paragraph::new(text_largest)
+        .left_aligned()
+        .render(col_largest, buf);
+    Paragraph::new(text_refresh_current)
+        .left_aligned()
+        .render(col_refresh_current, buf);
+    Paragraph::new(text_errors)
+        .left_aligned()
+        .render(col_errors, buf);
+    Paragraph::new(text_undo)
+        .left_aligned()
+        .render(col_undo, buf);
+    Paragraph::new(text_permanent)
+        .left_aligned()
+        .render(col_permanent, buf);
 }
@@ -0,0 +1,61 @@
+use crate::config::UIConfig;
+use ratatui::{prelude::*, widgets::*};
+use std::path::PathBuf;
+
+use crate::ui::constants::{
+    NORMAL_ROW_COLOR, TABLE_HEADER_BG, TABLE_HEADER_FG, TABLE_SIZE_WIDTH, TEXT_COLOR,
+    TEXT_PRE_DELETED_BG, TEXT_SELECTED_BG,
+};
+use crate::ui::utils::format_file_size;
+
+pub fn render_largest_files(
+    area: Rect,
+    buf: &mut Buffer,
+    files: &[(PathBuf, u64)],
+    cursor: usize,
+    config: &UIConfig,
+) {
+    let block = Block::default()
+        .title(format!(" Largest files: {} ", files.len()))
+        .padding(Padding::horizontal(1))
+        .borders(Borders::ALL)
+        .border_set(symbols::border::PROPORTIONAL_TALL)
+        .fg(TEXT_COLOR)
+        .bg(NORMAL_ROW_COLOR);
+
+    let header = ["Path", "Size"]
+        .into_iter()
+        .map(Cell::from)
+        .collect::<Row>()
+        .style(Style::default().fg(TABLE_HEADER_FG).bg(TABLE_HEADER_BG))
+        .height(1);
+
+    let selected_style = match config.confirming_deletion && !config.confirming_permanent {
+        true => Style::default().bg(TEXT_PRE_DELETED_BG),
+        false => Style::default().bg(TEXT_SELECTED_BG),
+    };
+
+    let rows = files.iter().map(|(path, size)| {
+        Row::new(vec![
+            Text::from(path.to_string_lossy().to_string()),
+            Text::from(format_file_size(*size)),
+        ])
+    });
+
+    let table = Table::new(
+        rows,
+        [Constraint::Fill(1), Constraint::Length(TABLE_SIZE_WIDTH)],
+    )
+    .block(block)
+    .header(header)
+    .highlight_symbol("> ")
+    .highlight_style(selected_style)
+    .highlight_spacing(HighlightSpacing::Always);
+
+    StatefulWidget::render(
+        table,
+        area,
+        buf,
+        &mut TableState::default().with_selected(Some(cursor)),
+    );
+}
@@ -6,14 +6,20 @@ use ratatui::widgets::*;
 mod chart;
 pub mod constants;
 mod content;
+mod errors;
 mod footer;
+mod largest;
+mod notification;
 mod path_bar;
+mod progress;
 mod title;
 mod utils;
 use constants::TEXT_TITLE;
-pub use content::{render_content, DebugData};
+pub use content::{render_content, ContentView, DebugData};
 pub use footer::render_footer;
+pub use notification::render_notification;
 pub use path_bar::render_path_bar;
+pub use progress::{render_delete_progress, render_progress};
 pub use title::render_title;
 
 use self::chart::render_chart;
@@ -23,6 +29,16 @@ impl<S: DataStore<DataStoreKey>> Widget for &mut App<S> {
     fn render(self, area: Rect, buf: &mut Buffer) {
         self.pre_render();
         let current_path = self.store.get_current_path().clone();
+        let largest_files = match self.ui_config.show_largest {
+            true => self.task_manager.largest_files.to_sorted_vec(),
+            false => vec![],
+        };
+        self.largest_cursor = self
+            .largest_cursor
+            .min(largest_files.len().saturating_sub(1));
+        self.errors_cursor = self
+            .errors_cursor
+            .min(self.store.get_errors().len().saturating_sub(1));
         let maybe_folder = self.store.get_current_folder();
 
         let mut chart_data = vec![];
@@ -38,12 +54,11 @@ impl<S: DataStore<DataStoreKey>> Widget for &mut App<S> {
         };
 
         // Main wrapper
-        let mut title = TEXT_TITLE;
+        let title = TEXT_TITLE;
         let mut border_color = TEXT_COLOR;
 
         if let Some(folder) = maybe_folder {
             if folder.has_error {
-                title = "Error";
                 border_color = TEXT_PRE_DELETED_BG;
             }
             chart_data = folder.get_chart_data(0.8, 5);
@@ -63,23 +78,51 @@ impl<S: DataStore<DataStoreKey>> Widget for &mut App<S> {
         let vertical = Layout::vertical([
             Constraint::Length(2), // Header - 2 lines
             Constraint::Length(1), // Path bar - 1 line
+            Constraint::Length(1), // Progress - 1 line
+            // Notification - 1 line when deletion failed
+            Constraint::Length(match self.delete_failures.is_empty() {
+                true => 0,
+                false => 1,
+            }),
             Constraint::Fill(1),   // Content - Fill the rest of the space
             Constraint::Length(4), // Chart - 4 lines
-            Constraint::Length(2), // Footer - 2 lines
+            Constraint::Length(3), // Footer - 3 lines
         ]);
-        let [header_area, path_area, rest_area, chart_area, footer_area] =
+        let [header_area, path_area, progress_area, notification_area, rest_area, chart_area, footer_area] =
             vertical.areas(inner_area);
 
-        render_title(header_area, buf, maybe_folder, &self.ui_config);
-        render_path_bar(path_area, buf, &current_path);
-        render_content(
-            rest_area,
+        render_title(
+            header_area,
             buf,
             maybe_folder,
+            self.store.get_errors().len(),
             &self.ui_config,
-            &self.logger,
-            &debug,
         );
+        render_path_bar(path_area, buf, &current_path);
+        if self.delete_manager.is_done() {
+            render_progress(
+                progress_area,
+                buf,
+                &self.task_manager.stats,
+                self.task_manager.is_working,
+            );
+        } else {
+            render_delete_progress(progress_area, buf, &self.delete_manager.progress);
+        }
+        render_notification(
+            notification_area,
+            buf,
+            &self.delete_failures,
+            self.ui_config.confirming_deletion && self.ui_config.confirming_permanent,
+        );
+        let view = if self.ui_config.show_errors {
+            ContentView::Errors(self.store.get_errors(), self.errors_cursor)
+        } else if self.ui_config.show_largest {
+            ContentView::LargestFiles(&largest_files, self.largest_cursor)
+        } else {
+            ContentView::Folder(maybe_folder)
+        };
+        render_content(rest_area, buf, view, &self.ui_config, &self.logger, &debug);
         render_chart(chart_area, buf, chart_data);
         render_footer(footer_area, buf);
     }
@@ -0,0 +1,58 @@
+use crate::delete_manager::DeleteFailure;
+use ratatui::{prelude::*, widgets::*};
+
+use super::constants::{TEXT_ERROR, TEXT_ICON_ERROR, TEXT_PRE_DELETED_BG};
+use super::utils::{color_capital_letter, format_file_size};
+
+/// `confirming` lists entries which are deleted permanently on the next `p` press
+pub fn render_notification(
+    area: Rect,
+    buf: &mut Buffer,
+    failures: &[DeleteFailure],
+    confirming: bool,
+) {
+    let Some(failure) = failures.first() else {
+        return;
+    };
+
+    if confirming {
+        let retry: Vec<&DeleteFailure> = failures.iter().filter(|f| f.in_trash).collect();
+        let size: u64 = retry.iter().map(|f| f.task.entry.size.unwrap_or(0)).sum();
+        let paths: Vec<String> = retry
+            .iter()
+            .map(|f| f.task.path().to_string_lossy().to_string())
+            .collect();
+        let text = format!(
+            "{TEXT_ICON_ERROR} Permanently delete {} ({}): {} | p - confirm | Esc - cancel",
+            retry.len(),
+            format_file_size(size),
+            paths.join(", ")
+        );
+        Paragraph::new(text)
+            .style(Style::default().fg(TEXT_PRE_DELETED_BG))
+            .left_aligned()
+            .render(area, buf);
+        return;
+    }
+
+    let mut text = format!(
+        "{TEXT_ICON_ERROR} Failed to delete {}: {:?} ({})",
+        failure.task.path().to_string_lossy(),
+        failure.kind,
+        failure.message
+    );
+    if failures.len() > 1 {
+        text.push_str(&format!(" and {} more", failures.len() - 1));
+    }
+
+    let mut spans = vec![Span::styled(text, Style::default().fg(TEXT_ERROR))];
+    if failures.iter().any(|failure| failure.in_trash) {
+        spans.push(Span::raw(" | "));
+        spans.extend(color_capital_letter("permanently delete - 2x".into(), None, None).spans);
+    }
+    spans.push(Span::raw(" | Esc - dismiss"));
+
+    Paragraph::new(Line::from(spans))
+        .left_aligned()
+        .render(area, buf);
+}
@@ -0,0 +1,83 @@
+use crate::delete_manager::DeleteProgress;
+use crate::task_manager::ScanStats;
+use ratatui::{prelude::*, widgets::*};
+use std::time::Duration;
+
+use super::constants::{TEXT_HIGHLIGHTED, TEXT_MARKER};
+use super::utils::format_file_size;
+
+fn format_duration(duration: Duration) -> String {
+    let seconds = duration.as_secs_f64();
+    if seconds >= 60.0 {
+        format!(
+            "{}m {:02}s",
+            duration.as_secs() / 60,
+            duration.as_secs() % 60
+        )
+    } else {
+        format!("{seconds:.1}s")
+    }
+}
+
+pub fn render_progress(area: Rect, buf: &mut Buffer, stats: &ScanStats, is_working: bool) {
+    let total = stats.total();
+    let elapsed = format_duration(stats.elapsed());
+
+    let mut parts = vec![
+        match is_working {
+            true => format!(
+                "Scanning: {} files, {} folders, {} | {elapsed}",
+                total.files,
+                total.directories,
+                format_file_size(total.bytes)
+            ),
+            false => format!(
+                "Scanned {} files, {} folders, {} in {elapsed}",
+                total.files,
+                total.directories,
+                format_file_size(total.bytes)
+            ),
+        },
+        format!(
+            "{:.0} files/s, {:.0} folders/s, {}/s",
+            stats.per_second(total.files),
+            stats.per_second(total.directories),
+            format_file_size(stats.per_second(total.bytes) as u64)
+        ),
+    ];
+    if is_working {
+        parts.push(format!("{} folders pending", total.directories_pending));
+        if let (Some(completion), Some(remaining)) =
+            (stats.completion(), stats.estimated_remaining())
+        {
+            parts.push(format!(
+                "ETA {} ({:.0}%)",
+                format_duration(remaining),
+                completion * 100.0
+            ));
+        }
+    }
+    if total.errors > 0 {
+        parts.push(format!("{} errors", total.errors));
+    }
+
+    Paragraph::new(parts.join(" | "))
+        .style(Style::default().fg(TEXT_MARKER))
+        .left_aligned()
+        .render(area, buf);
+}
+
+pub fn render_delete_progress(area: Rect, buf: &mut Buffer, progress: &DeleteProgress) {
+    let text = format!(
+        "Deleting: {}/{} entries, {} files, {} freed | Esc - cancel",
+        progress.tasks_done,
+        progress.tasks_total,
+        progress.files,
+        format_file_size(progress.bytes)
+    );
+
+    Paragraph::new(text)
+        .style(Style::default().fg(TEXT_HIGHLIGHTED))
+        .left_aligned()
+        .render(area, buf);
+}
@@ -1,8 +1,8 @@
 use crate::config::UIConfig;
-use crate::fs::Folder;
+use crate::fs::{Folder, SizeMode};
 use ratatui::{prelude::*, widgets::*};
 
-use crate::ui::utils::{format_file_size, value_to_box};
+use crate::ui::utils::{format_file_size, format_size_delta, value_to_box};
 
 use super::utils::color_capital_letter;
 
@@ -10,26 +10,64 @@ pub fn render_title(
     area: Rect,
     buf: &mut Buffer,
     maybe_folder: Option<&Folder>,
+    error_count: usize,
     ui_config: &UIConfig,
 ) {
-    let horizontal_layout = Layout::horizontal([Constraint::Fill(1), Constraint::Max(23)]);
+    let horizontal_layout = Layout::horizontal([Constraint::Fill(1), Constraint::Max(35)]);
     let [left_col, right_col] = horizontal_layout.areas(area);
 
     // Folder data
     if let Some(folder) = maybe_folder {
-        Paragraph::new(format!(
-            "{} | {}",
-            folder.title,
-            format_file_size(folder.get_size()),
-        ))
-        .bold()
-        .left_aligned()
-        .render(left_col, buf);
+        let mut text = format!("{} | {}", folder.title, format_file_size(folder.get_size()));
+        if ui_config.dry_run {
+            text.push_str(" | dry run");
+        }
+        if ui_config.diff {
+            text.push_str(&format!(
+                " | {} since older snapshot",
+                match folder.get_delta() {
+                    0 => "no change".to_string(),
+                    delta => format_size_delta(delta),
+                }
+            ));
+        } else if ui_config.read_only {
+            text.push_str(" | read-only dump");
+        }
+        if folder.entries.iter().any(|entry| entry.is_stale) {
+            text.push_str(" | cached, revalidating");
+        }
+        let marked = folder.get_marked_entries().len();
+        if marked > 0 {
+            text.push_str(&format!(
+                " | {marked} selected ({})",
+                format_file_size(folder.get_marked_size())
+            ));
+        }
+        if folder.hard_links > 0 {
+            text.push_str(&format!(" | {} hard links deduplicated", folder.hard_links));
+        }
+        if error_count > 0 {
+            text.push_str(&format!(" | {error_count} errors (E - list)"));
+        }
+        if ui_config.git {
+            text.push_str(&format!(
+                " | {} ignored",
+                format_file_size(folder.get_ignored_size())
+            ));
+        }
+        Paragraph::new(text)
+            .bold()
+            .left_aligned()
+            .render(left_col, buf);
     }
 
     // Settings
-    let config_layout = Layout::horizontal([Constraint::Max(12), Constraint::Max(11)]);
-    let [col_color, col_trash] = config_layout.areas(right_col);
+    let config_layout = Layout::horizontal([
+        Constraint::Max(12),
+        Constraint::Max(11),
+        Constraint::Max(12),
+    ]);
+    let [col_color, col_trash, col_blocks] = config_layout.areas(right_col);
 
     let text_color = color_capital_letter(
         "Colored: ".into(),
@@ -42,10 +80,19 @@ pub fn render_title(
         Some(value_to_box(&ui_config.move_to_trash)),
     );
 
+    let text_blocks = color_capital_letter(
+        "Blocks: ".into(),
+        None,
+        Some(value_to_box(&(ui_config.size_mode == SizeMode::Allocated))),
+    );
+
     Paragraph::new(text_color)
         .right_aligned()
         .render(col_color, buf);
     Paragraph::new(text_trash)
         .right_aligned()
         .render(col_trash, buf);
+    Paragraph::new(text_blocks)
+        .right_aligned()
+        .render(col_blocks, buf);
 }
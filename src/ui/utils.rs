@@ -1,10 +1,15 @@
 use crate::config::UIConfig;
 use crate::fs::Folder;
-use crate::fs::FolderEntryType;
+use crate::fs::{FolderEntryType, GitStatus, SizeChange, SizeChangeKind};
 use crate::ui::constants::{NORMAL_ROW_COLOR, TABLE_SPACE_WIDTH, TEXT_UNKNOWN};
 use ratatui::{prelude::*, widgets::*};
 
-use super::constants::{TEXT_HIGHLIGHTED, TEXT_ICON_FOLDER_ASCII};
+use super::constants::{
+    TEXT_ERROR, TEXT_HIGHLIGHTED, TEXT_ICON_ERROR, TEXT_ICON_FOLDER_ASCII, TEXT_ICON_LOCK,
+    TEXT_MARKER, TEXT_MARKER_ADDED, TEXT_MARKER_EXCLUDED, TEXT_MARKER_IGNORED,
+    TEXT_MARKER_MOUNT_POINT, TEXT_MARKER_REMOVED, TEXT_MARKER_STALE, TEXT_MARKER_UNTRACKED,
+    TEXT_PRE_DELETED_BG, TEXT_SHRUNK,
+};
 
 pub fn format_file_size(size: u64) -> String {
     const KB: u64 = 1024;
@@ -25,6 +30,24 @@ pub fn format_file_size(size: u64) -> String {
     }
 }
 
+/// Signed size, e.g. `+1.50 MB`, empty when nothing changed
+pub fn format_size_delta(delta: i64) -> String {
+    match delta {
+        0 => String::new(),
+        1.. => format!("+{}", format_file_size(delta.unsigned_abs())),
+        _ => format!("-{}", format_file_size(delta.unsigned_abs())),
+    }
+}
+
+fn size_change_to_cell<'a>(change: &SizeChange) -> Text<'a> {
+    let color = match change.delta {
+        1.. => TEXT_ERROR,
+        0 => TEXT_MARKER,
+        _ => TEXT_SHRUNK,
+    };
+    Text::from(format_size_delta(change.delta)).style(Style::default().fg(color))
+}
+
 pub fn calculate_color(percent: u64, _max_entry_size: u64) -> Color {
     let colors = [
         Color::Rgb(0, 128, 0),    // Green
@@ -57,11 +80,9 @@ pub fn folder_to_rows<'a>(folder: &'a Folder, config: &'a UIConfig) -> Vec<Row<'
         .map(|item| {
             let (item_size, bar, color) = match item.size {
                 Some(size) => {
-                    let percent = if max_entry_size == 0 {
-                        0
-                    } else {
-                        (size * TABLE_SPACE_WIDTH as u64 / max_entry_size).div_euclid(1)
-                    };
+                    let percent = (size * TABLE_SPACE_WIDTH as u64)
+                        .checked_div(max_entry_size)
+                        .unwrap_or(0);
                     let mut b = String::new();
                     let color = calculate_color(percent, max_entry_size);
                     for _ in 0..percent {
@@ -69,6 +90,21 @@ pub fn folder_to_rows<'a>(folder: &'a Folder, config: &'a UIConfig) -> Vec<Row<'
                     }
                     (Text::from(format_file_size(size)), Text::from(b), color)
                 }
+                None if item
+                    .change
+                    .is_some_and(|change| change.kind == SizeChangeKind::Removed) =>
+                {
+                    (
+                        Text::from(TEXT_MARKER_REMOVED),
+                        Text::from(" "),
+                        NORMAL_ROW_COLOR,
+                    )
+                }
+                None if item.is_excluded => (
+                    Text::from(TEXT_MARKER_EXCLUDED),
+                    Text::from(" "),
+                    NORMAL_ROW_COLOR,
+                ),
                 None => (Text::from(TEXT_UNKNOWN), Text::from(" "), NORMAL_ROW_COLOR),
             };
             let prefix = match item.kind == FolderEntryType::Folder {
@@ -81,12 +117,80 @@ pub fn folder_to_rows<'a>(folder: &'a Folder, config: &'a UIConfig) -> Vec<Row<'
                 bar_style = bar_style.fg(color);
             }
 
-            Row::new(vec![
-                prefix,
-                Text::from(item.title.clone()),
-                item_size,
-                bar.style(bar_style),
-            ])
+            let mut title = vec![Span::raw(item.title.clone())];
+            if item.is_mount_point {
+                title.push(Span::styled(
+                    format!(" ({TEXT_MARKER_MOUNT_POINT})"),
+                    Style::default().fg(TEXT_MARKER),
+                ));
+            }
+            if item.is_protected {
+                title.push(Span::styled(
+                    format!(" {TEXT_ICON_LOCK}"),
+                    Style::default().fg(TEXT_MARKER),
+                ));
+            }
+            if item.has_error {
+                title.push(Span::styled(
+                    format!(" {TEXT_ICON_ERROR}"),
+                    Style::default().fg(TEXT_ERROR),
+                ));
+            }
+            let git_marker = match item.git_status {
+                Some(GitStatus::Ignored) => Some(TEXT_MARKER_IGNORED),
+                Some(GitStatus::Untracked) => Some(TEXT_MARKER_UNTRACKED),
+                _ => None,
+            };
+            if item
+                .change
+                .is_some_and(|change| change.kind == SizeChangeKind::Added)
+            {
+                title.push(Span::styled(
+                    format!(" ({TEXT_MARKER_ADDED})"),
+                    Style::default().fg(TEXT_MARKER),
+                ));
+            }
+            if let Some(marker) = git_marker {
+                title.push(Span::styled(
+                    format!(" ({marker})"),
+                    Style::default().fg(TEXT_MARKER),
+                ));
+            }
+            if item.is_stale {
+                title.push(Span::styled(
+                    format!(" ({TEXT_MARKER_STALE})"),
+                    Style::default().fg(TEXT_MARKER),
+                ));
+            }
+
+            let mut cells = vec![prefix, Text::from(Line::from(title)), item_size];
+            if config.git {
+                cells.push(match item.ignored_size {
+                    0 => Text::from(" "),
+                    size => Text::from(format_file_size(size)),
+                });
+            }
+            if config.diff {
+                cells.push(match &item.change {
+                    Some(change) => size_change_to_cell(change),
+                    None => Text::from(" "),
+                });
+            }
+            cells.push(bar.style(bar_style));
+            let row = Row::new(cells);
+
+            if item.is_marked && config.confirming_deletion && !config.confirming_permanent {
+                row.style(Style::default().bg(TEXT_PRE_DELETED_BG))
+            } else if item.is_marked {
+                row.style(Style::default().fg(TEXT_HIGHLIGHTED))
+            } else if item.is_excluded
+                || item.is_stale
+                || item.size.is_none() && item.change.is_some()
+            {
+                row.style(Style::default().fg(TEXT_MARKER))
+            } else {
+                row
+            }
         })
         .collect()
 }
@@ -0,0 +1,52 @@
+use notify::{Event, RecommendedWatcher, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+
+/// Collects paths changed under watched root
+#[derive(Debug)]
+pub struct FsWatcher {
+    pub root: PathBuf,
+    // Stops watching when dropped
+    _watcher: RecommendedWatcher,
+    events: Receiver<notify::Result<Event>>,
+    pending: BTreeSet<PathBuf>,
+}
+
+impl FsWatcher {
+    pub fn new(root: &Path) -> notify::Result<Self> {
+        let (event_tx, event_rx) = channel();
+        let mut watcher = notify::recommended_watcher(event_tx)?;
+        watcher.watch(root, RecursiveMode::Recursive)?;
+
+        Ok(FsWatcher {
+            root: root.to_path_buf(),
+            _watcher: watcher,
+            events: event_rx,
+            pending: BTreeSet::new(),
+        })
+    }
+
+    /// Receives events without blocking, returns errors reported by the watcher
+    pub fn collect(&mut self) -> Vec<notify::Error> {
+        let mut errors = vec![];
+        for event in self.events.try_iter() {
+            match event {
+                // Every kind is handled the same way, current state of the path is read again
+                Ok(event) => self.pending.extend(
+                    event
+                        .paths
+                        .into_iter()
+                        .filter(|path| path.starts_with(&self.root) && path != &self.root),
+                ),
+                Err(err) => errors.push(err),
+            }
+        }
+        errors
+    }
+
+    /// Changed paths in order, parents before their content
+    pub fn take_changes(&mut self) -> Vec<PathBuf> {
+        std::mem::take(&mut self.pending).into_iter().collect()
+    }
+}
@@ -0,0 +1,221 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod audit_log {
+
+    use wiper::audit_log::{
+        parse_path_glob, parse_since, AuditEntryKind, AuditFilter, AuditLog, AuditMethod,
+        AuditRecord, AuditStatus,
+    };
+    use wiper::config::InitConfig;
+    use wiper::fs::DataStoreType;
+
+    use super::*;
+    use std::fs;
+    use std::io::Write;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - scan
+    ///     - folder
+    ///         - file.txt (100 bytes)
+    ///     - file.txt (100 bytes)
+    /// - deletions.log (created by the app)
+    const FILES: &[(&str, u64)] = &[
+        ("scan/folder/file.txt", TEST_FILE_SIZE),
+        ("scan/file.txt", TEST_FILE_SIZE),
+    ];
+
+    fn setup_app(path: &str, dry_run: bool) -> App<DataStoreType> {
+        setup_app_with(InitConfig {
+            file_path: Some(format!("{path}/scan")),
+            audit_log: Some(PathBuf::from(format!("{path}/deletions.log"))),
+            dry_run: Some(dry_run),
+            ..InitConfig::default()
+        })
+    }
+
+    fn delete_all(app: &mut App<DataStoreType>) {
+        app.on_mark_all();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(app);
+    }
+
+    fn read_records(path: &str) -> Vec<AuditRecord> {
+        AuditLog::new(PathBuf::from(format!("{path}/deletions.log")))
+            .read()
+            .unwrap()
+    }
+
+    fn record(path: &str, timestamp: &str, status: AuditStatus) -> AuditRecord {
+        AuditRecord {
+            timestamp: timestamp.into(),
+            path: PathBuf::from(path),
+            size: Some(TEST_FILE_SIZE),
+            kind: AuditEntryKind::File,
+            method: AuditMethod::Trash,
+            status,
+            error: None,
+        }
+    }
+
+    #[test]
+    fn records_every_deletion() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app(&path, false);
+
+        delete_all(&mut app);
+
+        let records = read_records(&path);
+        assert_eq!(records.len(), 2);
+        let folder = records
+            .iter()
+            .find(|record| record.kind == AuditEntryKind::Folder)
+            .unwrap();
+        assert!(folder.path.is_absolute());
+        assert!(folder.path.ends_with("scan/folder"));
+        assert_eq!(folder.size, Some(TEST_FILE_SIZE));
+        assert_eq!(folder.method, AuditMethod::Permanent);
+        assert_eq!(folder.status, AuditStatus::Deleted);
+        assert!(folder.time().is_some());
+        assert!(records.iter().all(|record| record.error.is_none()));
+    }
+
+    #[test]
+    fn records_failed_deletion() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app(&path, false);
+
+        fs::remove_file(format!("{path}/scan/file.txt")).unwrap();
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        let records = read_records(&path);
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].status, AuditStatus::Failed);
+        assert!(records[0].error.is_some());
+    }
+
+    #[test]
+    fn appends_across_sessions() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app(&path, false);
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        let mut app = setup_app(&path, false);
+        delete_all(&mut app);
+
+        let records = read_records(&path);
+        assert_eq!(records.len(), 2);
+        assert!(records[0].path.ends_with("scan/folder"));
+        assert!(records[1].path.ends_with("scan/file.txt"));
+    }
+
+    #[test]
+    fn does_not_record_dry_run() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app(&path, true);
+
+        delete_all(&mut app);
+
+        assert!(read_records(&path).is_empty());
+    }
+
+    #[test]
+    fn skips_malformed_lines() {
+        let path = create_testing_files(&[], FILES);
+        let log = AuditLog::new(PathBuf::from(format!("{path}/deletions.log")));
+        log.append(&record("/a", "2024-05-01T10:00:00Z", AuditStatus::Deleted))
+            .unwrap();
+        fs::OpenOptions::new()
+            .append(true)
+            .open(&log.path)
+            .unwrap()
+            .write_all(b"not a record\n")
+            .unwrap();
+        log.append(&record("/b", "2024-05-02T10:00:00Z", AuditStatus::Deleted))
+            .unwrap();
+
+        let records = log.read().unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[1].path, PathBuf::from("/b"));
+    }
+
+    #[test]
+    fn filters_records() {
+        let old = record(
+            "/home/user/old.txt",
+            "2024-05-01T10:00:00Z",
+            AuditStatus::Deleted,
+        );
+        let failed = record(
+            "/home/user/project/target",
+            "2024-05-10T10:00:00Z",
+            AuditStatus::Failed,
+        );
+        let cancelled = record("/tmp/cache", "2024-05-20T10:00:00Z", AuditStatus::Cancelled);
+        let records = [old, failed, cancelled];
+        let matching = |filter: AuditFilter| -> Vec<String> {
+            records
+                .iter()
+                .filter(|record| filter.matches(record))
+                .map(|record| record.path.to_string_lossy().to_string())
+                .collect()
+        };
+
+        assert_eq!(matching(AuditFilter::default()).len(), 3);
+        assert_eq!(
+            matching(AuditFilter {
+                since: Some(parse_since("2024-05-05").unwrap()),
+                ..AuditFilter::default()
+            }),
+            vec!["/home/user/project/target", "/tmp/cache"]
+        );
+        assert_eq!(
+            matching(AuditFilter {
+                path: Some(parse_path_glob("/home/user/**").unwrap()),
+                failed_only: true,
+                ..AuditFilter::default()
+            }),
+            vec!["/home/user/project/target"]
+        );
+        assert!(matching(AuditFilter {
+            since: Some(parse_since("7days").unwrap()),
+            ..AuditFilter::default()
+        })
+        .is_empty());
+        assert!(parse_since("yesterday-ish").is_err());
+    }
+
+    #[test]
+    fn binary_prints_filtered_log() {
+        let path = create_testing_files(&[], FILES);
+        let log_path = format!("{path}/deletions.log");
+        let log = AuditLog::new(PathBuf::from(&log_path));
+        log.append(&record("/a", "2024-05-01T10:00:00Z", AuditStatus::Deleted))
+            .unwrap();
+        log.append(&record("/b", "2024-05-02T10:00:00Z", AuditStatus::Failed))
+            .unwrap();
+
+        let output = Command::new(env!("CARGO_BIN_EXE_wiper"))
+            .args(["log", "--audit-log", &log_path, "--failed"])
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        assert_eq!(
+            String::from_utf8_lossy(&output.stdout),
+            "2024-05-02T10:00:00Z\tfailed\ttrash\t100\t/b\n"
+        );
+    }
+}
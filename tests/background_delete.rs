@@ -0,0 +1,150 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod background_delete {
+
+    use wiper::fs::{DataStoreType, FolderEntryType};
+
+    use super::*;
+    use std::fs;
+    use std::path::Path;
+
+    const TEST_FILE_SIZE: u64 = 100;
+    const FOLDERS: u64 = 10;
+    const FILES_PER_FOLDER: u64 = 30;
+
+    /// - big
+    ///     - folder_0 .. folder_9
+    ///         - file_0.txt .. file_29.txt (100 bytes each)
+    /// - file.txt (100 bytes)
+    fn testing_files() -> Vec<(String, u64)> {
+        let mut files = vec![("file.txt".to_string(), TEST_FILE_SIZE)];
+        for folder in 0..FOLDERS {
+            for file in 0..FILES_PER_FOLDER {
+                files.push((
+                    format!("big/folder_{folder}/file_{file}.txt"),
+                    TEST_FILE_SIZE,
+                ));
+            }
+        }
+        files
+    }
+
+    fn size_on_disk(path: &Path) -> u64 {
+        fs::read_dir(path)
+            .unwrap()
+            .map(|entry| {
+                let path = entry.unwrap().path();
+                match path.is_dir() {
+                    true => size_on_disk(&path),
+                    false => path.metadata().unwrap().len(),
+                }
+            })
+            .sum()
+    }
+
+    fn select_big_folder(app: &mut App<DataStoreType>) {
+        app.on_cursor_down();
+        assert_eq!(
+            get_current_folder(app).unwrap().get_selected_entry().title,
+            "big"
+        );
+    }
+
+    #[test]
+    fn deletes_in_background() {
+        let path = create_testing_files(&[], &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        select_big_folder(&mut app);
+        app.on_delete();
+        app.on_delete();
+        assert!(!app.delete_manager.is_done());
+        assert!(!app.ui_config.confirming_deletion);
+
+        handle_tasks_synchronously(&mut app);
+        assert!(!Path::new(&format!("{path}/big")).exists());
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::Folder).len(), 0);
+        assert_eq!(get_current_folder(&app).unwrap().get_size(), TEST_FILE_SIZE);
+    }
+
+    #[test]
+    fn reports_removed_files_and_bytes() {
+        let path = create_testing_files(&[], &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        select_big_folder(&mut app);
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        let progress = &app.delete_manager.progress;
+        assert_eq!(progress.files, FOLDERS * FILES_PER_FOLDER);
+        assert_eq!(progress.bytes, FOLDERS * FILES_PER_FOLDER * TEST_FILE_SIZE);
+        assert_eq!(progress.tasks_done, 1);
+        assert_eq!(progress.tasks_total, 1);
+    }
+
+    #[test]
+    fn reports_bytes_in_current_size_mode() {
+        let path = create_testing_files(&[], &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+        app.on_toggle_size_mode();
+        handle_tasks_synchronously(&mut app);
+
+        select_big_folder(&mut app);
+        let big_size = get_entry_by_title(&app, "big").size.unwrap();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.delete_manager.progress.bytes, big_size);
+    }
+
+    #[test]
+    fn keeps_store_consistent_after_cancel() {
+        let path = create_testing_files(&[], &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        select_big_folder(&mut app);
+        app.on_delete();
+        app.on_delete();
+        app.on_escape();
+        handle_tasks_synchronously(&mut app);
+
+        // Folder is removed partially, or not at all if cancelled before start
+        let big_path = Path::new(&*path).join("big");
+        if big_path.exists() {
+            assert_eq!(
+                get_entry_by_title(&app, "big").size,
+                Some(size_on_disk(&big_path))
+            );
+        } else {
+            assert_eq!(get_entry_by_kind(&app, FolderEntryType::Folder).len(), 0);
+        }
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            size_on_disk(Path::new(&*path))
+        );
+    }
+
+    #[test]
+    fn drops_entry_removed_before_deletion() {
+        let path = create_testing_files(&[], &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        fs::remove_file(format!("{path}/file.txt")).unwrap();
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 0);
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            FOLDERS * FILES_PER_FOLDER * TEST_FILE_SIZE
+        );
+    }
+}
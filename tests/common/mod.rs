@@ -1,3 +1,8 @@
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{ErrorKind, Write};
+use std::ops::Deref;
+use std::path::Path;
 use std::thread;
 use std::time::Duration;
 use wiper::app::App;
@@ -9,8 +14,9 @@ pub const TEST_FILE_PATH_EDIT: &str = "./tests/test_files/edit";
 pub fn setup_app_view<S: DataStore<DataStoreKey>>() -> App<S> {
     let c = InitConfig {
         file_path: Some(TEST_FILE_PATH_VIEW.to_string()),
+        ..InitConfig::default()
     };
-    let mut app: App<S> = App::new(c);
+    let mut app: App<S> = App::new(c).unwrap();
     app.ui_config.open_file = false;
     app.ui_config.sort_by = SortBy::Title;
     app.init();
@@ -20,8 +26,9 @@ pub fn setup_app_view<S: DataStore<DataStoreKey>>() -> App<S> {
 pub fn setup_app_edit<S: DataStore<DataStoreKey>>(postfix: &str) -> App<S> {
     let c = InitConfig {
         file_path: Some(format!("{TEST_FILE_PATH_EDIT}_{postfix}")),
+        ..InitConfig::default()
     };
-    let mut app: App<S> = App::new(c);
+    let mut app: App<S> = App::new(c).unwrap();
     app.ui_config.open_file = false;
     app.ui_config.move_to_trash = false;
     app.ui_config.sort_by = SortBy::Title;
@@ -30,13 +37,111 @@ pub fn setup_app_edit<S: DataStore<DataStoreKey>>(postfix: &str) -> App<S> {
 }
 
 pub fn handle_tasks_synchronously<S: DataStore<DataStoreKey>>(app: &mut App<S>) {
-    while !app.task_manager.is_done() {
+    while !app.task_manager.is_done() || !app.delete_manager.is_done() {
         app.tick();
         thread::sleep(Duration::from_millis(10));
     }
     app.pre_render();
 }
 
+/// Files are not opened or moved to trash and entries are sorted by title, unless the config
+/// sets them. The scan is left running
+pub fn start_app_with<S: DataStore<DataStoreKey>>(config: InitConfig) -> App<S> {
+    let c = InitConfig {
+        open_file: config.open_file.or(Some(false)),
+        move_to_trash: config.move_to_trash.or(Some(false)),
+        sort_by: config.sort_by.or(Some(SortBy::Title)),
+        ..config
+    };
+    let mut app: App<S> = App::new(c).unwrap();
+    app.init();
+    app
+}
+
+/// Same as `start_app_with`, but returns once the scan is finished
+pub fn setup_app_with<S: DataStore<DataStoreKey>>(config: InitConfig) -> App<S> {
+    let mut app = start_app_with(config);
+    handle_tasks_synchronously(&mut app);
+    app
+}
+
+/// Folder of the running test, removed when dropped so a failed test cleans up as well
+pub struct TestFiles {
+    path: String,
+}
+
+impl TestFiles {
+    /// Config scanning the folder
+    pub fn config(&self) -> InitConfig {
+        InitConfig {
+            file_path: Some(self.path.clone()),
+            ..InitConfig::default()
+        }
+    }
+}
+
+impl Deref for TestFiles {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        &self.path
+    }
+}
+
+impl fmt::Display for TestFiles {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.path)
+    }
+}
+
+impl Drop for TestFiles {
+    fn drop(&mut self) {
+        match fs::remove_dir_all(&self.path) {
+            // Test may remove the folder itself
+            Err(err) if err.kind() != ErrorKind::NotFound => {
+                eprintln!("Failed to remove test folder: {err}");
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Creates folder named after the running test with provided empty folders and files of
+/// provided sizes
+#[must_use = "test folder is removed when dropped"]
+pub fn create_testing_files<P: AsRef<str>>(folders: &[&str], files: &[(P, u64)]) -> TestFiles {
+    // Tests run in threads named after them
+    let test_name = thread::current()
+        .name()
+        .unwrap_or("main")
+        .replace("::", "_");
+    let custom_folder = format!("{TEST_FILE_PATH_EDIT}_{test_name}");
+    // Left by an interrupted run
+    let _ = fs::remove_dir_all(&custom_folder);
+    fs::create_dir_all(&custom_folder).expect("Failed to create test folder");
+    for folder in folders {
+        fs::create_dir_all(format!("{custom_folder}/{folder}"))
+            .expect("Failed to create test folder");
+    }
+    for (file_path, size) in files {
+        if let Some(parent) = Path::new(file_path.as_ref()).parent() {
+            fs::create_dir_all(format!("{custom_folder}/{}", parent.display()))
+                .expect("Failed to create test folder");
+        }
+        write_file(&custom_folder, file_path.as_ref(), *size);
+    }
+    TestFiles {
+        path: custom_folder,
+    }
+}
+
+pub fn write_file(folder: &str, file_path: &str, size: u64) {
+    let mut file =
+        File::create(format!("{folder}/{file_path}")).expect("Failed to create test file");
+    file.write_all(&vec![b'x'; size as usize])
+        .expect("Failed to write to test file");
+}
+
 pub fn assert_item_at_index_is<S: DataStore<DataStoreKey>>(
     app: &App<S>,
     index: usize,
@@ -144,3 +249,14 @@ pub fn assert_root_view_folder_sorted_by_size<S: DataStore<DataStoreKey>>(app: &
 pub fn get_current_folder<S: DataStore<DataStoreKey>>(app: &App<S>) -> Option<&Folder> {
     app.store.get_current_folder()
 }
+
+pub fn get_entry_by_title<S: DataStore<DataStoreKey>>(app: &App<S>, title: &str) -> FolderEntry {
+    app.store
+        .get_current_folder()
+        .unwrap()
+        .entries
+        .iter()
+        .find(|e| e.title == title)
+        .cloned()
+        .unwrap_or_else(|| panic!("No entry with title {title}"))
+}
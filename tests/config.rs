@@ -0,0 +1,499 @@
+pub mod common;
+
+use crate::common::*;
+use clap::error::ErrorKind;
+use wiper::config::{ConfigError, InitConfig, UIConfig};
+use wiper::fs::SortBy;
+
+mod config {
+    use super::*;
+    use std::collections::HashMap;
+    use std::fs;
+    use std::process::Command;
+
+    fn build_with_env(args: &[&str], env: &[(&str, &str)]) -> Result<InitConfig, ConfigError> {
+        let env: HashMap<String, String> = env
+            .iter()
+            .map(|(name, value)| (name.to_string(), value.to_string()))
+            .collect();
+        InitConfig::build_with_env(
+            std::iter::once("wiper")
+                .chain(args.iter().copied())
+                .map(String::from),
+            |name| env.get(name).cloned(),
+        )
+    }
+
+    fn build(args: &[&str]) -> Result<InitConfig, ConfigError> {
+        build_with_env(args, &[])
+    }
+
+    fn args_error_kind(result: Result<InitConfig, ConfigError>) -> ErrorKind {
+        match result {
+            Err(ConfigError::Args(err)) => err.kind(),
+            other => panic!("Expected arguments error, got {other:?}"),
+        }
+    }
+
+    /// Creates config file at provided path of the test folder
+    fn create_config_file_at(file_path: &str, content: &str) -> TestFiles {
+        let folder = create_testing_files(&[], &[(file_path, 0)]);
+        fs::write(format!("{folder}/{file_path}"), content).expect("Failed to write config file");
+        folder
+    }
+
+    /// Creates config file in its own folder, which serves as config home
+    fn create_config_file(content: &str) -> TestFiles {
+        create_config_file_at("wiper/config.toml", content)
+    }
+
+    #[test]
+    fn uses_defaults_without_arguments() {
+        let config = build(&[]).unwrap();
+        let ui_config = UIConfig::new(&config);
+
+        assert_eq!(config.file_path, None);
+        assert!(!ui_config.colored);
+        assert!(!ui_config.confirming_deletion);
+        assert_eq!(ui_config.sort_by, SortBy::Title);
+        assert!(ui_config.move_to_trash);
+        assert!(ui_config.open_file);
+        assert!(!ui_config.debug_enabled);
+    }
+
+    #[test]
+    fn reads_positional_path() {
+        let config = build(&[TEST_FILE_PATH_VIEW]).unwrap();
+
+        assert_eq!(config.file_path, Some(TEST_FILE_PATH_VIEW.to_string()));
+    }
+
+    #[test]
+    fn reads_flags_for_every_ui_field() {
+        let config = build(&[
+            "--colored",
+            "--sort",
+            "size",
+            "--no-trash",
+            "--no-open-file",
+            "--debug",
+            TEST_FILE_PATH_VIEW,
+        ])
+        .unwrap();
+        let ui_config = UIConfig::new(&config);
+
+        assert!(ui_config.colored);
+        assert_eq!(ui_config.sort_by, SortBy::Size);
+        assert!(!ui_config.move_to_trash);
+        assert!(!ui_config.open_file);
+        assert!(ui_config.debug_enabled);
+    }
+
+    #[test]
+    fn reads_short_flags() {
+        let config = build(&["-c", "-s", "size"]).unwrap();
+
+        assert_eq!(config.colored, Some(true));
+        assert_eq!(config.sort_by, Some(SortBy::Size));
+    }
+
+    #[test]
+    fn last_of_paired_flags_wins() {
+        let config = build(&["--trash", "--no-trash", "--no-colored", "--colored"]).unwrap();
+
+        assert_eq!(config.move_to_trash, Some(false));
+        assert_eq!(config.colored, Some(true));
+    }
+
+    #[test]
+    fn leaves_unset_flags_empty() {
+        let config = build(&["--debug"]).unwrap();
+
+        assert_eq!(config.colored, None);
+        assert_eq!(config.sort_by, None);
+        assert_eq!(config.move_to_trash, None);
+        assert_eq!(config.open_file, None);
+        assert_eq!(config.debug_enabled, Some(true));
+    }
+
+    #[test]
+    fn rejects_unknown_sort_order() {
+        assert_eq!(
+            args_error_kind(build(&["--sort", "date"])),
+            ErrorKind::InvalidValue
+        );
+    }
+
+    #[test]
+    fn rejects_unknown_flag() {
+        assert_eq!(
+            args_error_kind(build(&["--bogus"])),
+            ErrorKind::UnknownArgument
+        );
+    }
+
+    #[test]
+    fn rejects_missing_directory() {
+        assert_eq!(
+            args_error_kind(build(&["./tests/test_files/missing"])),
+            ErrorKind::ValueValidation
+        );
+    }
+
+    #[test]
+    fn rejects_file_as_directory() {
+        assert_eq!(
+            args_error_kind(build(&["./tests/test_files/view/a_root_file.txt"])),
+            ErrorKind::ValueValidation
+        );
+    }
+
+    #[test]
+    fn returns_help_and_version_as_errors() {
+        assert_eq!(args_error_kind(build(&["--help"])), ErrorKind::DisplayHelp);
+        assert_eq!(
+            args_error_kind(build(&["--version"])),
+            ErrorKind::DisplayVersion
+        );
+    }
+
+    #[test]
+    fn binary_exits_with_error_code_on_invalid_arguments() {
+        let output = Command::new(env!("CARGO_BIN_EXE_wiper"))
+            .arg("--sort=date")
+            .output()
+            .unwrap();
+
+        assert_eq!(output.status.code(), Some(2));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("--sort"));
+    }
+
+    #[test]
+    fn binary_prints_version() {
+        let output = Command::new(env!("CARGO_BIN_EXE_wiper"))
+            .arg("--version")
+            .output()
+            .unwrap();
+
+        assert!(output.status.success());
+        assert!(String::from_utf8_lossy(&output.stdout).contains(env!("CARGO_PKG_VERSION")));
+    }
+
+    #[test]
+    fn reads_config_file_from_xdg_config_home() {
+        let config_home = create_config_file("colored = true\nsort_by = \"size\"\n");
+
+        let config = build_with_env(&[], &[("XDG_CONFIG_HOME", &*config_home)]).unwrap();
+
+        assert_eq!(config.colored, Some(true));
+        assert_eq!(config.sort_by, Some(SortBy::Size));
+        assert_eq!(config.move_to_trash, None);
+    }
+
+    #[test]
+    fn reads_config_file_from_home_fallback() {
+        let home = create_config_file_at(".config/wiper/config.toml", "debug_enabled = true\n");
+
+        let config = build_with_env(&[], &[("HOME", &*home)]).unwrap();
+
+        assert_eq!(config.debug_enabled, Some(true));
+    }
+
+    #[test]
+    fn reads_config_file_from_argument() {
+        let folder = create_config_file("move_to_trash = false\nopen_file = false\n");
+        let config_path = format!("{folder}/wiper/config.toml");
+
+        let config = build(&["--config", &config_path]).unwrap();
+
+        assert_eq!(config.move_to_trash, Some(false));
+        assert_eq!(config.open_file, Some(false));
+    }
+
+    #[test]
+    fn environment_overrides_config_file() {
+        let config_home = create_config_file("colored = true\nsort_by = \"size\"\n");
+
+        let config = build_with_env(
+            &[],
+            &[
+                ("XDG_CONFIG_HOME", &*config_home),
+                ("WIPER_COLORED", "false"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(config.colored, Some(false));
+        assert_eq!(config.sort_by, Some(SortBy::Size));
+    }
+
+    #[test]
+    fn arguments_override_environment_and_config_file() {
+        let config_home = create_config_file("sort_by = \"size\"\ncolored = false\n");
+
+        let config = build_with_env(
+            &["--sort", "title", "--colored"],
+            &[
+                ("XDG_CONFIG_HOME", &*config_home),
+                ("WIPER_SORT_BY", "size"),
+                ("WIPER_COLORED", "0"),
+            ],
+        )
+        .unwrap();
+
+        assert_eq!(config.sort_by, Some(SortBy::Title));
+        assert_eq!(config.colored, Some(true));
+    }
+
+    #[test]
+    fn ignores_missing_default_config_file() {
+        let config =
+            build_with_env(&[], &[("XDG_CONFIG_HOME", "./tests/test_files/missing")]).unwrap();
+
+        assert_eq!(config.colored, None);
+    }
+
+    #[test]
+    fn rejects_missing_explicit_config_file() {
+        let result = build(&["--config", "./tests/test_files/missing.toml"]);
+
+        assert!(matches!(result, Err(ConfigError::File { .. })));
+    }
+
+    #[test]
+    fn rejects_malformed_config_file() {
+        let config_home = create_config_file("colored = \"maybe\"\n");
+
+        let err = build_with_env(&[], &[("XDG_CONFIG_HOME", &*config_home)]).unwrap_err();
+
+        assert!(matches!(err, ConfigError::File { .. }));
+        assert!(err.to_string().contains("config.toml"));
+    }
+
+    #[test]
+    fn rejects_unknown_config_keys() {
+        let config_home = create_config_file("colour = true\n");
+
+        let err = build_with_env(&[], &[("XDG_CONFIG_HOME", &*config_home)]).unwrap_err();
+
+        assert!(err.to_string().contains("colour"));
+    }
+
+    #[test]
+    fn rejects_malformed_environment_variable() {
+        let result = build_with_env(&[], &[("WIPER_MOVE_TO_TRASH", "sometimes")]);
+
+        assert!(matches!(result, Err(ConfigError::Env { .. })));
+    }
+
+    #[test]
+    fn binary_reports_malformed_config_file() {
+        let folder = create_config_file("sort_by = 1\n");
+        let config_path = format!("{folder}/wiper/config.toml");
+
+        let output = Command::new(env!("CARGO_BIN_EXE_wiper"))
+            .args(["--config", &config_path])
+            .output()
+            .unwrap();
+
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid config file"));
+    }
+
+    #[test]
+    fn combines_exclude_patterns_from_config_file_and_arguments() {
+        let config_home = create_config_file("exclude = [\"node_modules\"]\n");
+
+        let config = build_with_env(
+            &["--exclude", "*.log", "-e", "target"],
+            &[("XDG_CONFIG_HOME", &*config_home)],
+        )
+        .unwrap();
+
+        assert_eq!(config.exclude, vec!["node_modules", "*.log", "target"]);
+    }
+
+    #[test]
+    fn rejects_invalid_exclude_pattern() {
+        assert_eq!(
+            args_error_kind(build(&["--exclude", "a[b"])),
+            ErrorKind::ValueValidation
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_exclude_pattern_in_config_file() {
+        let config_home = create_config_file("exclude = [\"a[b\"]\n");
+
+        let err = build_with_env(&[], &[("XDG_CONFIG_HOME", &*config_home)]).unwrap_err();
+
+        assert!(matches!(err, ConfigError::File { .. }));
+        assert!(err.to_string().contains("a[b"));
+    }
+
+    #[test]
+    fn reads_git_mode_from_every_source() {
+        let config_home = create_config_file("git = true\n");
+
+        let from_file = build_with_env(&[], &[("XDG_CONFIG_HOME", &*config_home)]).unwrap();
+        let from_env = build_with_env(&[], &[("WIPER_GIT", "yes")]).unwrap();
+        let from_args =
+            build_with_env(&["--no-git"], &[("XDG_CONFIG_HOME", &*config_home)]).unwrap();
+
+        assert!(UIConfig::new(&from_file).git);
+        assert_eq!(from_env.git, Some(true));
+        assert_eq!(from_args.git, Some(false));
+        assert_eq!(build(&["-g"]).unwrap().git, Some(true));
+    }
+
+    #[test]
+    fn reads_dry_run_from_every_source() {
+        let config_home = create_config_file("dry_run = true\n");
+
+        let from_file = build_with_env(&[], &[("XDG_CONFIG_HOME", &*config_home)]).unwrap();
+        let from_env = build_with_env(&[], &[("WIPER_DRY_RUN", "1")]).unwrap();
+        let from_args =
+            build_with_env(&["--no-dry-run"], &[("XDG_CONFIG_HOME", &*config_home)]).unwrap();
+
+        assert!(UIConfig::new(&from_file).dry_run);
+        assert_eq!(from_env.dry_run, Some(true));
+        assert_eq!(from_args.dry_run, Some(false));
+        assert_eq!(build(&["--dry-run"]).unwrap().dry_run, Some(true));
+        assert!(!UIConfig::new(&build(&[]).unwrap()).dry_run);
+    }
+
+    #[test]
+    fn combines_protect_patterns_from_config_file_and_arguments() {
+        let config_home = create_config_file("protect = [\"~/.ssh\"]\n");
+
+        let config = build_with_env(
+            &["--protect", "*.key"],
+            &[("XDG_CONFIG_HOME", &*config_home)],
+        )
+        .unwrap();
+
+        assert_eq!(config.protect, vec!["~/.ssh", "*.key"]);
+        assert_eq!(
+            args_error_kind(build(&["--protect", "a[b"])),
+            ErrorKind::ValueValidation
+        );
+    }
+
+    #[test]
+    fn resolves_audit_log_path() {
+        let config_home = create_config_file("audit_log = \"/tmp/from_file.log\"\n");
+
+        let from_file = build_with_env(&[], &[("XDG_CONFIG_HOME", &*config_home)]).unwrap();
+        let from_env = build_with_env(
+            &[],
+            &[
+                ("XDG_CONFIG_HOME", &*config_home),
+                ("WIPER_AUDIT_LOG", "/tmp/from_env.log"),
+            ],
+        )
+        .unwrap();
+        let from_args = build_with_env(
+            &["--audit-log", "/tmp/from_args.log"],
+            &[("WIPER_AUDIT_LOG", "/tmp/from_env.log")],
+        )
+        .unwrap();
+        let from_state_home = build_with_env(&[], &[("XDG_STATE_HOME", "/tmp/state")]).unwrap();
+        let from_home = build_with_env(&[], &[("HOME", "/home/user")]).unwrap();
+
+        assert_eq!(from_file.audit_log, Some("/tmp/from_file.log".into()));
+        assert_eq!(from_env.audit_log, Some("/tmp/from_env.log".into()));
+        assert_eq!(from_args.audit_log, Some("/tmp/from_args.log".into()));
+        assert_eq!(
+            from_state_home.audit_log,
+            Some("/tmp/state/wiper/deletions.log".into())
+        );
+        assert_eq!(
+            from_home.audit_log,
+            Some("/home/user/.local/state/wiper/deletions.log".into())
+        );
+        assert_eq!(build(&[]).unwrap().audit_log, None);
+    }
+
+    #[test]
+    fn parses_log_subcommand() {
+        let config = build(&["log", "--since", "2024-05-01", "--failed"]).unwrap();
+        let Some(wiper::config::Command::Log(args)) = config.command else {
+            panic!("Expected log subcommand, got {:?}", config.command);
+        };
+        assert!(args.since.is_some());
+        assert!(args.failed);
+        assert!(args.path.is_none());
+
+        assert!(build(&[]).unwrap().command.is_none());
+        assert_eq!(
+            args_error_kind(build(&["log", "--since", "someday"])),
+            ErrorKind::ValueValidation
+        );
+    }
+
+    #[test]
+    fn reads_watch_option() {
+        assert_eq!(build(&["-w"]).unwrap().watch, Some(true));
+        assert_eq!(
+            build_with_env(&[], &[("WIPER_WATCH", "yes")])
+                .unwrap()
+                .watch,
+            Some(true)
+        );
+        assert_eq!(
+            build_with_env(&["--no-watch"], &[("WIPER_WATCH", "yes")])
+                .unwrap()
+                .watch,
+            Some(false)
+        );
+        assert_eq!(
+            args_error_kind(build(&["--watch", "--export", "json"])),
+            ErrorKind::ArgumentConflict
+        );
+    }
+
+    #[test]
+    fn reads_cache_options() {
+        let config_home = create_config_file("cache = true\ncache_dir = \"/tmp/from_file\"\n");
+
+        let from_file = build_with_env(&[], &[("XDG_CONFIG_HOME", &*config_home)]).unwrap();
+        let from_env = build_with_env(
+            &[],
+            &[
+                ("XDG_CONFIG_HOME", &*config_home),
+                ("WIPER_CACHE", "no"),
+                ("WIPER_CACHE_DIR", "/tmp/from_env"),
+            ],
+        )
+        .unwrap();
+        let from_args = build_with_env(
+            &["--cache", "--cache-dir", "/tmp/from_args"],
+            &[("WIPER_CACHE", "no"), ("WIPER_CACHE_DIR", "/tmp/from_env")],
+        )
+        .unwrap();
+
+        assert_eq!(from_file.cache, Some(true));
+        assert_eq!(from_file.cache_dir, Some("/tmp/from_file".into()));
+        assert_eq!(from_env.cache, Some(false));
+        assert_eq!(from_env.cache_dir, Some("/tmp/from_env".into()));
+        assert_eq!(from_args.cache, Some(true));
+        assert_eq!(from_args.cache_dir, Some("/tmp/from_args".into()));
+        assert_eq!(
+            build_with_env(&[], &[("XDG_CACHE_HOME", "/tmp/cache")])
+                .unwrap()
+                .cache_dir,
+            Some("/tmp/cache/wiper".into())
+        );
+        assert_eq!(
+            build_with_env(&[], &[("HOME", "/home/user")])
+                .unwrap()
+                .cache_dir,
+            Some("/home/user/.cache/wiper".into())
+        );
+        assert_eq!(build(&[]).unwrap().cache, None);
+        assert_eq!(
+            args_error_kind(build(&["--cache", "--import", "dump.json"])),
+            ErrorKind::ArgumentConflict
+        );
+    }
+}
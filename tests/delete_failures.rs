@@ -0,0 +1,137 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod delete_failures {
+
+    use wiper::delete_manager::{DeleteFailure, DeleteTask};
+    use wiper::fs::{DataStore, DataStoreType, FolderEntryType};
+    use wiper::logger::MessageLevel;
+
+    use super::*;
+    use std::fs;
+    use std::io::ErrorKind;
+    use std::path::Path;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - file_1.txt (100 bytes)
+    /// - file_2.txt (100 bytes)
+    const FILES: &[(&str, u64)] = &[
+        ("file_1.txt", TEST_FILE_SIZE),
+        ("file_2.txt", TEST_FILE_SIZE),
+    ];
+
+    fn delete_failure(app: &mut App<DataStoreType>, title: &str, in_trash: bool) -> DeleteFailure {
+        DeleteFailure {
+            task: DeleteTask {
+                folder_path: app.store.get_current_path().clone(),
+                entry: get_entry_by_title(app, title),
+            },
+            kind: ErrorKind::Other,
+            message: "trash is not available".into(),
+            in_trash,
+        }
+    }
+
+    #[test]
+    fn surfaces_failed_deletion() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        fs::remove_file(format!("{path}/file_1.txt")).unwrap();
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.delete_failures.len(), 1);
+        let failure = &app.delete_failures[0];
+        assert_eq!(failure.kind, ErrorKind::NotFound);
+        assert!(failure.task.path().ends_with("file_1.txt"));
+        assert!(!failure.in_trash);
+        assert!(app.logger.messages.iter().any(|(_, level, message)| {
+            matches!(level, MessageLevel::Error) && message.contains("file_1.txt")
+        }));
+        assert!(!app.ui_config.confirming_deletion);
+    }
+
+    #[test]
+    fn dismisses_failures_on_escape() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        fs::remove_file(format!("{path}/file_1.txt")).unwrap();
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.delete_failures.len(), 1);
+
+        app.on_escape();
+        assert!(app.delete_failures.is_empty());
+    }
+
+    #[test]
+    fn retries_trash_failures_permanently() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        let failure = delete_failure(&mut app, "file_1.txt", true);
+        app.delete_failures.push(failure);
+        app.on_retry_permanently();
+        handle_tasks_synchronously(&mut app);
+
+        // First press only asks for confirmation
+        assert!(app.ui_config.confirming_deletion);
+        assert!(app.ui_config.confirming_permanent);
+        assert!(Path::new(&format!("{path}/file_1.txt")).exists());
+        assert_eq!(app.delete_failures.len(), 1);
+
+        app.on_retry_permanently();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(!app.ui_config.confirming_deletion);
+        assert!(app.delete_failures.is_empty());
+        assert!(!Path::new(&format!("{path}/file_1.txt")).exists());
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 1);
+        assert_eq!(get_current_folder(&app).unwrap().get_size(), TEST_FILE_SIZE);
+    }
+
+    #[test]
+    fn keeps_other_failures_on_retry() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        let failure = delete_failure(&mut app, "file_1.txt", false);
+        app.delete_failures.push(failure);
+        app.on_retry_permanently();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.delete_failures.len(), 1);
+        assert!(Path::new(&format!("{path}/file_1.txt")).exists());
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 2);
+    }
+
+    #[test]
+    fn does_not_mix_permanent_and_regular_confirmation() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        let failure = delete_failure(&mut app, "file_1.txt", true);
+        app.delete_failures.push(failure);
+        app.on_retry_permanently();
+        app.on_cursor_down();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        // Deletion of selected entry waits for its own confirmation
+        assert!(app.ui_config.confirming_deletion);
+        assert!(!app.ui_config.confirming_permanent);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 2);
+
+        app.on_retry_permanently();
+        handle_tasks_synchronously(&mut app);
+        assert!(Path::new(&format!("{path}/file_1.txt")).exists());
+    }
+}
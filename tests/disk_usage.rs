@@ -0,0 +1,112 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+use wiper::config::InitConfig;
+use wiper::fs::SizeMode;
+
+mod disk_usage {
+
+    use wiper::fs::DataStoreType;
+
+    use super::*;
+    use std::fs::File;
+
+    const SPARSE_FILE_SIZE: u64 = 16 * 1024 * 1024;
+    const DENSE_FILE_SIZE: u64 = 100;
+
+    /// - sparse.img (16 MB, nothing written)
+    /// - folder
+    ///     - sparse.img (16 MB, nothing written)
+    /// - dense.txt (100 bytes)
+    fn create_sparse_files() -> TestFiles {
+        let path = create_testing_files(&["folder"], &[("dense.txt", DENSE_FILE_SIZE)]);
+        for sparse_path in ["sparse.img", "folder/sparse.img"] {
+            let file =
+                File::create(format!("{path}/{sparse_path}")).expect("Failed to create test file");
+            file.set_len(SPARSE_FILE_SIZE)
+                .expect("Failed to extend test file");
+        }
+        path
+    }
+
+    fn setup_app(path: String, size_mode: SizeMode) -> App<DataStoreType> {
+        setup_app_with(InitConfig {
+            file_path: Some(path),
+            size_mode: Some(size_mode),
+            ..InitConfig::default()
+        })
+    }
+
+    #[test]
+    fn reports_apparent_size_by_default() {
+        let path = create_sparse_files();
+        let app = setup_app(path.to_string(), SizeMode::default());
+
+        assert_eq!(app.ui_config.size_mode, SizeMode::Apparent);
+        assert_eq!(
+            get_entry_by_title(&app, "sparse.img").size,
+            Some(SPARSE_FILE_SIZE)
+        );
+        assert_eq!(
+            get_entry_by_title(&app, "dense.txt").size,
+            Some(DENSE_FILE_SIZE)
+        );
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            SPARSE_FILE_SIZE * 2 + DENSE_FILE_SIZE
+        );
+    }
+
+    #[test]
+    fn reports_allocated_size() {
+        let path = create_sparse_files();
+        let app = setup_app(path.to_string(), SizeMode::Allocated);
+
+        let sparse_size = get_entry_by_title(&app, "sparse.img").size.unwrap();
+        let dense_size = get_entry_by_title(&app, "dense.txt").size.unwrap();
+        let folder_size = get_entry_by_title(&app, "folder").size.unwrap();
+
+        assert!(sparse_size < SPARSE_FILE_SIZE / 16);
+        assert!(folder_size < SPARSE_FILE_SIZE / 16);
+        assert!(dense_size >= DENSE_FILE_SIZE);
+        assert_eq!(dense_size % 512, 0);
+    }
+
+    #[test]
+    fn toggles_size_mode_and_rescans() {
+        let path = create_sparse_files();
+        let mut app = setup_app(path.to_string(), SizeMode::Apparent);
+
+        assert_eq!(
+            get_entry_by_title(&app, "folder").size,
+            Some(SPARSE_FILE_SIZE)
+        );
+
+        app.on_toggle_size_mode();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.ui_config.size_mode, SizeMode::Allocated);
+        assert!(get_entry_by_title(&app, "folder").size.unwrap() < SPARSE_FILE_SIZE / 16);
+
+        app.on_toggle_size_mode();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.ui_config.size_mode, SizeMode::Apparent);
+        assert_eq!(
+            get_entry_by_title(&app, "folder").size,
+            Some(SPARSE_FILE_SIZE)
+        );
+    }
+
+    #[test]
+    fn reports_allocated_size_for_parent_listing() {
+        let path = create_sparse_files();
+        let mut app = setup_app(format!("{path}/folder"), SizeMode::Allocated);
+        assert!(get_entry_by_title(&app, "sparse.img").size.unwrap() < SPARSE_FILE_SIZE / 16);
+
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+        assert!(get_entry_by_title(&app, "sparse.img").size.unwrap() < SPARSE_FILE_SIZE / 16);
+        assert!(get_entry_by_title(&app, "dense.txt").size.unwrap() >= DENSE_FILE_SIZE);
+    }
+}
@@ -0,0 +1,147 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod dry_run {
+
+    use wiper::config::InitConfig;
+    use wiper::fs::{DataStoreType, FolderEntryType};
+
+    use super::*;
+    use std::path::Path;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - folder
+    ///     - file_1.txt .. file_3.txt (100 bytes each)
+    /// - file.txt (100 bytes)
+    const FILES: &[(&str, u64)] = &[
+        ("folder/file_1.txt", TEST_FILE_SIZE),
+        ("folder/file_2.txt", TEST_FILE_SIZE),
+        ("folder/file_3.txt", TEST_FILE_SIZE),
+        ("file.txt", TEST_FILE_SIZE),
+    ];
+
+    fn setup_app_dry_run(path: &TestFiles) -> App<DataStoreType> {
+        setup_app_with(InitConfig {
+            dry_run: Some(true),
+            ..path.config()
+        })
+    }
+
+    fn delete_all(app: &mut App<DataStoreType>) {
+        app.on_mark_all();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(app);
+    }
+
+    #[test]
+    fn keeps_files_on_disk() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app_dry_run(&path);
+
+        delete_all(&mut app);
+
+        assert!(Path::new(&format!("{path}/file.txt")).exists());
+        assert!(Path::new(&format!("{path}/folder/file_1.txt")).exists());
+        assert_eq!(get_current_folder(&app).unwrap().entries.len(), 1);
+        assert_eq!(get_current_folder(&app).unwrap().get_size(), 0);
+    }
+
+    #[test]
+    fn updates_parent_folder_sizes() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app_dry_run(&path);
+
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(
+            get_entry_by_title(&app, "folder").size,
+            Some(TEST_FILE_SIZE * 2)
+        );
+    }
+
+    #[test]
+    fn hides_simulated_deletions_after_navigation() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app_dry_run(&path);
+
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(get_current_folder(&app).unwrap().cursor_index, 1);
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 0);
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 3
+        );
+    }
+
+    #[test]
+    fn summarizes_simulated_deletions() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app_dry_run(&path);
+
+        delete_all(&mut app);
+
+        let summary = app.dry_run_summary().unwrap();
+        let lines: Vec<&str> = summary.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines.iter().any(|line| line.starts_with("300\t")
+            && line.ends_with(&format!("{}/folder", path.trim_start_matches("./")))));
+        assert!(lines
+            .iter()
+            .any(|line| line.starts_with("100\t") && line.ends_with("file.txt")));
+        assert_eq!(lines[2], "400\ttotal");
+    }
+
+    #[test]
+    fn keeps_simulated_deletions_hidden_after_rescan() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app_dry_run(&path);
+
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+        app.reset();
+        handle_tasks_synchronously(&mut app);
+        app.on_refresh_current();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 0);
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 3
+        );
+        let summary = app.dry_run_summary().unwrap();
+        assert_eq!(summary.lines().count(), 2);
+        assert!(summary.ends_with("100\ttotal"));
+    }
+
+    #[test]
+    fn has_no_summary_outside_of_dry_run() {
+        let path = create_testing_files(&[], FILES);
+        let app: App<DataStoreType> = setup_app_with(path.config());
+
+        assert!(app.dry_run_summary().is_none());
+    }
+}
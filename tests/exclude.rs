@@ -0,0 +1,178 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+use wiper::config::InitConfig;
+
+mod exclude {
+
+    use wiper::fs::{DataStore, DataStoreType, FolderEntryType};
+
+    use super::*;
+    use std::fs;
+
+    const TEST_FILE_SIZE: u64 = 100;
+    const ROOT_IGNORE_FILE: &str = "*.tmp\n# comment\n";
+    const SUB_IGNORE_FILE: &str = "build\n";
+
+    /// - node_modules
+    ///     - dep.js
+    /// - src
+    ///     - main.rs
+    ///     - debug.log
+    /// - sub
+    ///     - build
+    ///         - out.bin
+    ///     - .wiperignore (build)
+    ///     - file.txt
+    /// - .wiperignore (*.tmp)
+    /// - app.log
+    /// - cache.tmp
+    /// - keep.txt
+    const FILES: &[(&str, u64)] = &[
+        ("node_modules/dep.js", TEST_FILE_SIZE),
+        ("src/main.rs", TEST_FILE_SIZE),
+        ("src/debug.log", TEST_FILE_SIZE),
+        ("sub/build/out.bin", TEST_FILE_SIZE),
+        ("sub/file.txt", TEST_FILE_SIZE),
+        ("app.log", TEST_FILE_SIZE),
+        ("cache.tmp", TEST_FILE_SIZE),
+        ("keep.txt", TEST_FILE_SIZE),
+    ];
+
+    fn create_files_with_ignore_files() -> TestFiles {
+        let path = create_testing_files(&[], FILES);
+        fs::write(format!("{path}/.wiperignore"), ROOT_IGNORE_FILE)
+            .expect("Failed to write ignore file");
+        fs::write(format!("{path}/sub/.wiperignore"), SUB_IGNORE_FILE)
+            .expect("Failed to write ignore file");
+        path
+    }
+
+    fn setup_app(path: String, exclude: &[&str]) -> App<DataStoreType> {
+        setup_app_with(InitConfig {
+            file_path: Some(path),
+            exclude: exclude.iter().map(|pattern| pattern.to_string()).collect(),
+            ..InitConfig::default()
+        })
+    }
+
+    fn has_entry(app: &App<DataStoreType>, title: &str) -> bool {
+        get_current_folder(app)
+            .unwrap()
+            .entries
+            .iter()
+            .any(|entry| entry.title == title)
+    }
+
+    #[test]
+    fn skips_excluded_files() {
+        let path = create_files_with_ignore_files();
+        let app = setup_app(path.to_string(), &["node_modules", "*.log"]);
+
+        assert!(!has_entry(&app, "app.log"));
+        assert!(has_entry(&app, "keep.txt"));
+        assert_eq!(get_entry_by_title(&app, "src").size, Some(TEST_FILE_SIZE));
+    }
+
+    #[test]
+    fn marks_excluded_folders_without_size() {
+        let path = create_files_with_ignore_files();
+        let mut app = setup_app(path.to_string(), &["node_modules", "*.log"]);
+
+        let node_modules = get_entry_by_title(&app, "node_modules");
+        assert_eq!(node_modules.kind, FolderEntryType::Folder);
+        assert!(node_modules.is_excluded);
+        assert_eq!(node_modules.size, None);
+        assert!(!app
+            .store
+            .get_keys()
+            .iter()
+            .any(|key| key.ends_with("node_modules/dep.js")));
+
+        let expected_size =
+            TEST_FILE_SIZE * 3 + ROOT_IGNORE_FILE.len() as u64 + SUB_IGNORE_FILE.len() as u64;
+        assert_eq!(get_current_folder(&app).unwrap().get_size(), expected_size);
+    }
+
+    #[test]
+    fn applies_ignore_files_to_their_subtree() {
+        let path = create_files_with_ignore_files();
+        let mut app = setup_app(path.to_string(), &[]);
+
+        assert!(!has_entry(&app, "cache.tmp"));
+        assert!(has_entry(&app, "app.log"));
+        assert!(!get_entry_by_title(&app, "node_modules").is_excluded);
+
+        while get_current_folder(&app).unwrap().get_selected_entry().title != "sub" {
+            app.on_cursor_down();
+        }
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(get_entry_by_title(&app, "build").is_excluded);
+        assert_eq!(
+            get_entry_by_title(&app, "file.txt").size,
+            Some(TEST_FILE_SIZE)
+        );
+    }
+
+    #[test]
+    fn does_not_enter_excluded_folder() {
+        let path = create_files_with_ignore_files();
+        let mut app = setup_app(path.to_string(), &["node_modules"]);
+        let root_path = app.store.get_current_path().clone();
+
+        while get_current_folder(&app).unwrap().get_selected_entry().title != "node_modules" {
+            app.on_cursor_down();
+        }
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.store.get_current_path(), &root_path);
+    }
+
+    #[test]
+    fn excludes_entries_when_listing_parent() {
+        let path = create_files_with_ignore_files();
+        let mut app = setup_app(format!("{path}/src"), &["node_modules", "*.log"]);
+
+        assert!(!has_entry(&app, "debug.log"));
+
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(get_entry_by_title(&app, "node_modules").is_excluded);
+        assert!(!has_entry(&app, "app.log"));
+        assert!(!has_entry(&app, "cache.tmp"));
+        assert!(has_entry(&app, "keep.txt"));
+        assert!(get_entry_by_title(&app, "sub").size.unwrap() >= TEST_FILE_SIZE);
+    }
+
+    #[test]
+    fn applies_ignore_files_of_folders_above_root() {
+        let path = create_files_with_ignore_files();
+        for file_path in ["src/scratch.tmp", "sub/notes.tmp", "sub/build/out.tmp"] {
+            fs::write(format!("{path}/{file_path}"), "x").expect("Failed to write test file");
+        }
+
+        let app = setup_app(format!("{path}/src"), &[]);
+        assert!(!has_entry(&app, "scratch.tmp"));
+        assert!(has_entry(&app, "main.rs"));
+
+        let mut app = setup_app(format!("{path}/sub/build"), &[]);
+        assert!(!has_entry(&app, "out.tmp"));
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+        assert!(!has_entry(&app, "notes.tmp"));
+        assert!(has_entry(&app, "file.txt"));
+    }
+
+    #[test]
+    fn returns_error_for_invalid_pattern() {
+        let c = InitConfig {
+            exclude: vec!["[".into()],
+            ..InitConfig::default()
+        };
+        assert!(App::<DataStoreType>::new(c).is_err());
+    }
+}
@@ -0,0 +1,157 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod export {
+
+    use wiper::config::InitConfig;
+    use wiper::export::{ExportEntryKind, ExportTree};
+    use wiper::fs::{DataStore, DataStoreType};
+
+    use super::*;
+    use std::fs;
+    use std::process::Command;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - node_modules
+    ///     - dep.js
+    /// - src
+    ///     - main.rs
+    ///     - lib.rs
+    /// - notes.txt
+    const FILES: &[(&str, u64)] = &[
+        ("node_modules/dep.js", TEST_FILE_SIZE),
+        ("src/main.rs", TEST_FILE_SIZE),
+        ("src/lib.rs", TEST_FILE_SIZE),
+        ("notes.txt", TEST_FILE_SIZE),
+    ];
+
+    fn export(app: &mut App<DataStoreType>) -> ExportTree {
+        let root = app.store.get_current_path().clone();
+        ExportTree::from_store(&app.store, &root)
+    }
+
+    #[test]
+    fn exports_whole_tree() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let tree = export(&mut app);
+
+        let root = &tree.root;
+        assert_eq!(root.name, "view");
+        assert!(root.path.is_absolute());
+        assert_eq!(root.kind, ExportEntryKind::Folder);
+        assert_eq!(
+            root.size,
+            Some(get_current_folder(&app).unwrap().get_size())
+        );
+        assert_eq!(root.file_types.as_ref().unwrap()["txt"], root.size.unwrap());
+        assert!(tree.errors.is_empty());
+
+        let children = root.children.as_ref().unwrap();
+        let titles: Vec<&str> = children.iter().map(|entry| entry.name.as_str()).collect();
+        assert_eq!(
+            titles,
+            vec![
+                "a_folder",
+                "b_folder",
+                "c_folder",
+                "a_root_file.txt",
+                "d_root_file.txt",
+                "z_root_file.txt"
+            ]
+        );
+
+        let a_folder = &children[0];
+        assert_eq!(a_folder.size, get_entry_by_title(&app, "a_folder").size);
+        assert!(a_folder.path.ends_with("view/a_folder"));
+        let nested = a_folder.children.as_ref().unwrap();
+        assert_eq!(nested.len(), 2);
+        assert_eq!(nested[0].kind, ExportEntryKind::File);
+        assert!(nested[0].children.is_none());
+        assert_eq!(
+            nested.iter().filter_map(|entry| entry.size).sum::<u64>(),
+            a_folder.size.unwrap()
+        );
+    }
+
+    #[test]
+    fn exports_excluded_folder_without_content() {
+        let path = create_testing_files(&[], FILES);
+        let c = InitConfig {
+            exclude: vec!["node_modules".into()],
+            ..path.config()
+        };
+        let mut app: App<DataStoreType> = App::new(c).unwrap();
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        let tree = export(&mut app);
+
+        let children = tree.root.children.as_ref().unwrap();
+        let node_modules = &children[0];
+        assert_eq!(node_modules.name, "node_modules");
+        assert!(node_modules.is_excluded);
+        assert_eq!(node_modules.size, None);
+        assert!(node_modules.children.is_none());
+
+        let src = &children[1];
+        assert_eq!(src.size, Some(TEST_FILE_SIZE * 2));
+        assert_eq!(src.file_types.as_ref().unwrap()["rs"], TEST_FILE_SIZE * 2);
+        assert_eq!(tree.root.size, Some(TEST_FILE_SIZE * 3));
+    }
+
+    #[test]
+    fn exports_scan_errors() {
+        let mut app: App<DataStoreType> = setup_app_with(InitConfig {
+            file_path: Some(format!("{TEST_FILE_PATH_EDIT}_export_missing")),
+            ..InitConfig::default()
+        });
+        let tree = export(&mut app);
+
+        assert_eq!(tree.errors.len(), 1);
+        assert_eq!(tree.errors[0].kind, "NotFound");
+        assert!(tree.errors[0].path.ends_with("edit_export_missing"));
+    }
+
+    #[test]
+    fn binary_writes_json_export() {
+        let path = create_testing_files(&[], FILES);
+        let output_path = format!("{path}.json");
+
+        let output = Command::new(env!("CARGO_BIN_EXE_wiper"))
+            .args(["--export", "json", "-o", &output_path, &path])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        assert!(output.stdout.is_empty());
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&output_path).unwrap()).unwrap();
+        assert_eq!(json["root"]["kind"], "folder");
+        assert_eq!(json["root"]["size"], TEST_FILE_SIZE * 4);
+        assert_eq!(json["root"]["children"].as_array().unwrap().len(), 3);
+        assert_eq!(json["root"]["children"][1]["children"][0]["name"], "lib.rs");
+        assert_eq!(json["errors"].as_array().unwrap().len(), 0);
+
+        let output = Command::new(env!("CARGO_BIN_EXE_wiper"))
+            .args(["--export", "json", &path])
+            .output()
+            .unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["root"]["size"], TEST_FILE_SIZE * 4);
+
+        fs::remove_file(output_path).unwrap();
+    }
+
+    #[test]
+    fn binary_requires_export_for_output() {
+        let output = Command::new(env!("CARGO_BIN_EXE_wiper"))
+            .args(["-o", "out.json"])
+            .output()
+            .unwrap();
+
+        assert_eq!(output.status.code(), Some(2));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("--export"));
+    }
+}
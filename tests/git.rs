@@ -0,0 +1,188 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+use wiper::config::InitConfig;
+
+mod git {
+
+    use wiper::fs::{parse_index, DataStoreType, GitStatus};
+
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    const TEST_FILE_SIZE: u64 = 100;
+    const GITIGNORE: &str = "target/\n*.log\n!keep.log\n";
+    const TRACKED_FILES: [&str; 3] = ["README.md", "src/main.rs", "target/tracked.bin"];
+
+    /// Builds version 2 index listing provided paths, with empty stats and object ids
+    fn build_index(paths: &[&str]) -> Vec<u8> {
+        let mut index = b"DIRC".to_vec();
+        index.extend_from_slice(&2u32.to_be_bytes());
+        index.extend_from_slice(&(paths.len() as u32).to_be_bytes());
+        for path in paths {
+            let entry_start = index.len();
+            index.extend_from_slice(&[0; 40 + 20]);
+            index.extend_from_slice(&(path.len() as u16).to_be_bytes());
+            index.extend_from_slice(path.as_bytes());
+            let entry_len = index.len() - entry_start;
+            index.resize(entry_start + (entry_len + 8) / 8 * 8, 0);
+        }
+        index
+    }
+
+    /// - .git
+    ///     - index (README.md, src/main.rs, target/tracked.bin)
+    /// - src
+    ///     - main.rs
+    ///     - debug.log
+    /// - target
+    ///     - out.bin
+    ///     - tracked.bin
+    /// - .gitignore (target/, *.log, !keep.log)
+    /// - README.md
+    /// - keep.log
+    /// - notes.txt
+    const FILES: &[(&str, u64)] = &[
+        ("src/main.rs", TEST_FILE_SIZE),
+        ("src/debug.log", TEST_FILE_SIZE),
+        ("target/out.bin", TEST_FILE_SIZE),
+        ("target/tracked.bin", TEST_FILE_SIZE),
+        ("README.md", TEST_FILE_SIZE),
+        ("keep.log", TEST_FILE_SIZE),
+        ("notes.txt", TEST_FILE_SIZE),
+    ];
+
+    fn create_repository() -> TestFiles {
+        let path = create_testing_files(&[".git"], FILES);
+        fs::write(format!("{path}/.gitignore"), GITIGNORE).expect("Failed to write ignore file");
+        fs::write(format!("{path}/.git/index"), build_index(&TRACKED_FILES))
+            .expect("Failed to write git index");
+        path
+    }
+
+    fn setup_app(path: String, git: bool) -> App<DataStoreType> {
+        setup_app_with(InitConfig {
+            file_path: Some(path),
+            git: Some(git),
+            ..InitConfig::default()
+        })
+    }
+
+    fn enter_folder(app: &mut App<DataStoreType>, title: &str) {
+        while get_current_folder(app).unwrap().get_selected_entry().title != title {
+            app.on_cursor_down();
+        }
+        app.on_enter();
+        handle_tasks_synchronously(app);
+    }
+
+    #[test]
+    fn classifies_entries() {
+        let path = create_repository();
+        let app = setup_app(path.to_string(), true);
+
+        let status = |title: &str| get_entry_by_title(&app, title).git_status;
+        assert_eq!(status("README.md"), Some(GitStatus::Tracked));
+        assert_eq!(status("src"), Some(GitStatus::Tracked));
+        assert_eq!(status("notes.txt"), Some(GitStatus::Untracked));
+        assert_eq!(status("keep.log"), Some(GitStatus::Untracked));
+        assert_eq!(status(".gitignore"), Some(GitStatus::Untracked));
+        assert_eq!(status(".git"), None);
+    }
+
+    #[test]
+    fn sums_ignored_sizes_per_folder() {
+        let path = create_repository();
+        let app = setup_app(path.to_string(), true);
+
+        assert_eq!(get_entry_by_title(&app, "src").ignored_size, TEST_FILE_SIZE);
+        assert_eq!(
+            get_entry_by_title(&app, "target").ignored_size,
+            TEST_FILE_SIZE
+        );
+        assert_eq!(get_entry_by_title(&app, "notes.txt").ignored_size, 0);
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_ignored_size(),
+            TEST_FILE_SIZE * 2
+        );
+    }
+
+    #[test]
+    fn tracked_files_inside_ignored_folder_stay_tracked() {
+        let path = create_repository();
+        let mut app = setup_app(path.to_string(), true);
+
+        assert_eq!(
+            get_entry_by_title(&app, "target").git_status,
+            Some(GitStatus::Tracked)
+        );
+        enter_folder(&mut app, "target");
+
+        let status = |title: &str| get_entry_by_title(&app, title).git_status;
+        assert_eq!(status("tracked.bin"), Some(GitStatus::Tracked));
+        assert_eq!(status("out.bin"), Some(GitStatus::Ignored));
+    }
+
+    #[test]
+    fn classifies_entries_when_listing_parent() {
+        let path = create_repository();
+        let mut app = setup_app(format!("{path}/src"), true);
+
+        assert_eq!(
+            get_entry_by_title(&app, "debug.log").git_status,
+            Some(GitStatus::Ignored)
+        );
+
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(
+            get_entry_by_title(&app, "notes.txt").git_status,
+            Some(GitStatus::Untracked)
+        );
+        assert_eq!(
+            get_entry_by_title(&app, "target").ignored_size,
+            TEST_FILE_SIZE
+        );
+        assert_eq!(get_entry_by_title(&app, "src").ignored_size, TEST_FILE_SIZE);
+    }
+
+    #[test]
+    fn does_nothing_when_disabled() {
+        let path = create_repository();
+        let app = setup_app(path.to_string(), false);
+
+        assert_eq!(get_entry_by_title(&app, "README.md").git_status, None);
+        assert_eq!(get_current_folder(&app).unwrap().get_ignored_size(), 0);
+    }
+
+    #[test]
+    fn reads_index_written_by_git() {
+        let path = create_repository();
+        fs::remove_dir_all(format!("{path}/.git")).expect("Failed to remove git folder");
+
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .arg("-C")
+                .arg(&*path)
+                .args(args)
+                .output()
+        };
+        if !git(&["init", "--quiet"]).is_ok_and(|output| output.status.success()) {
+            eprintln!("git is not available, skipping");
+            return;
+        }
+        git(&[&["add", "--force", "--"][..], &TRACKED_FILES[..]].concat())
+            .expect("Failed to add files to index");
+
+        let index = fs::read(format!("{path}/.git/index")).expect("Failed to read git index");
+        let mut paths = parse_index(&index, 20).expect("Failed to parse git index");
+        paths.sort();
+        assert_eq!(
+            paths,
+            TRACKED_FILES.iter().map(PathBuf::from).collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,129 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod hard_links {
+
+    use wiper::config::InitConfig;
+    use wiper::fs::DataStoreType;
+
+    use super::*;
+    use std::fs;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - folder_a
+    ///     - original.txt (100 bytes)
+    ///     - link_a.txt -> folder_a/original.txt
+    /// - folder_b
+    ///     - link_b.txt -> folder_a/original.txt
+    ///     - plain.txt (100 bytes)
+    /// - link_root.txt -> folder_a/original.txt
+    /// - plain.txt (100 bytes)
+    const FILES: &[(&str, u64)] = &[
+        ("folder_a/original.txt", TEST_FILE_SIZE),
+        ("folder_b/plain.txt", TEST_FILE_SIZE),
+        ("plain.txt", TEST_FILE_SIZE),
+    ];
+
+    fn create_files_with_links() -> TestFiles {
+        let path = create_testing_files(&[], FILES);
+        let original = format!("{path}/folder_a/original.txt");
+        for link in [
+            "folder_a/link_a.txt",
+            "folder_b/link_b.txt",
+            "link_root.txt",
+        ] {
+            fs::hard_link(&original, format!("{path}/{link}")).expect("Failed to create hard link");
+        }
+        path
+    }
+
+    #[test]
+    fn counts_hard_linked_file_once() {
+        let path = create_files_with_links();
+        let app: App<DataStoreType> = setup_app_with(path.config());
+
+        let root = get_current_folder(&app).unwrap();
+        assert_eq!(root.get_size(), TEST_FILE_SIZE * 3);
+
+        let linked_sizes = get_entry_by_title(&app, "folder_a").size.unwrap()
+            + get_entry_by_title(&app, "folder_b").size.unwrap()
+            + get_entry_by_title(&app, "link_root.txt").size.unwrap();
+        assert_eq!(linked_sizes, TEST_FILE_SIZE * 2);
+    }
+
+    #[test]
+    fn counts_plain_files_as_usual() {
+        let path = create_files_with_links();
+        let app: App<DataStoreType> = setup_app_with(path.config());
+
+        assert_eq!(
+            get_entry_by_title(&app, "plain.txt").size,
+            Some(TEST_FILE_SIZE)
+        );
+        assert!(get_entry_by_title(&app, "folder_b").size.unwrap() >= TEST_FILE_SIZE);
+    }
+
+    #[test]
+    fn reports_deduplicated_links_per_folder() {
+        let path = create_files_with_links();
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        assert_eq!(get_current_folder(&app).unwrap().hard_links, 3);
+
+        let mut nested_links = 0;
+        for title in ["folder_a", "folder_b"] {
+            app.on_cursor_down();
+            assert_eq!(
+                get_current_folder(&app).unwrap().get_selected_entry().title,
+                title
+            );
+            app.on_enter();
+            handle_tasks_synchronously(&mut app);
+            nested_links += get_current_folder(&app).unwrap().hard_links;
+            app.on_backspace();
+            handle_tasks_synchronously(&mut app);
+        }
+
+        let root_links = if get_entry_by_title(&app, "link_root.txt").size == Some(0) {
+            1
+        } else {
+            0
+        };
+        assert_eq!(nested_links + root_links, 3);
+    }
+
+    #[test]
+    fn counts_links_of_folder_above_scan_root_once() {
+        let path = create_files_with_links();
+        let mut app: App<DataStoreType> = setup_app_with(InitConfig {
+            file_path: Some(format!("{path}/folder_a")),
+            ..InitConfig::default()
+        });
+
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(get_entry_by_title(&app, "link_root.txt").size, Some(0));
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 3
+        );
+    }
+
+    #[test]
+    fn keeps_totals_after_reset() {
+        let path = create_files_with_links();
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        app.reset();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 3
+        );
+        assert_eq!(get_current_folder(&app).unwrap().hard_links, 3);
+    }
+}
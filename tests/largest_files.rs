@@ -0,0 +1,167 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod largest_files {
+
+    use wiper::fs::{DataStore, DataStoreType, SortBy};
+    use wiper::task_manager::LargestFiles;
+
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - docs
+    ///     - notes.txt (x3)
+    /// - project
+    ///     - src
+    ///         - main.rs (x4)
+    ///     - README.md (x1)
+    /// - root.txt (x2)
+    const FILES: &[(&str, u64)] = &[
+        ("docs/notes.txt", TEST_FILE_SIZE * 3),
+        ("project/src/main.rs", TEST_FILE_SIZE * 4),
+        ("project/README.md", TEST_FILE_SIZE),
+        ("root.txt", TEST_FILE_SIZE * 2),
+    ];
+
+    fn relative_largest_files(app: &mut App<DataStoreType>) -> Vec<(String, u64)> {
+        let root = app.store.get_current_path().clone();
+        app.task_manager
+            .largest_files
+            .to_sorted_vec()
+            .into_iter()
+            .map(|(path, size)| {
+                let path = path.strip_prefix(&root).unwrap().to_string_lossy();
+                (path.to_string(), size)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn lists_largest_files_of_whole_tree() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        assert_eq!(
+            relative_largest_files(&mut app),
+            vec![
+                ("project/src/main.rs".to_string(), TEST_FILE_SIZE * 4),
+                ("docs/notes.txt".to_string(), TEST_FILE_SIZE * 3),
+                ("root.txt".to_string(), TEST_FILE_SIZE * 2),
+                ("project/README.md".to_string(), TEST_FILE_SIZE),
+            ]
+        );
+    }
+
+    #[test]
+    fn keeps_only_largest_files_up_to_limit() {
+        let mut files = LargestFiles::new(2);
+        files.push(PathBuf::from("/a"), 10);
+        files.push(PathBuf::from("/b"), 30);
+        files.push(PathBuf::from("/c"), 20);
+        files.push(PathBuf::from("/d"), 5);
+        assert_eq!(
+            files.to_sorted_vec(),
+            vec![(PathBuf::from("/b"), 30), (PathBuf::from("/c"), 20)]
+        );
+
+        // Size of listed path is replaced
+        files.push(PathBuf::from("/c"), 40);
+        assert_eq!(
+            files.to_sorted_vec(),
+            vec![(PathBuf::from("/c"), 40), (PathBuf::from("/b"), 30)]
+        );
+
+        // Even when it shrinks below every listed one
+        files.push(PathBuf::from("/b"), 1);
+        assert_eq!(
+            files.to_sorted_vec(),
+            vec![(PathBuf::from("/c"), 40), (PathBuf::from("/b"), 1)]
+        );
+
+        files.remove(&PathBuf::from("/b"));
+        assert_eq!(files.to_sorted_vec(), vec![(PathBuf::from("/c"), 40)]);
+    }
+
+    #[test]
+    fn jumps_to_folder_of_selected_file() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+        let root = app.store.get_current_path().clone();
+
+        app.on_toggle_largest();
+        assert!(app.ui_config.show_largest);
+        app.on_cursor_down();
+        app.on_cursor_up();
+        app.on_cursor_down();
+        app.on_enter();
+        app.pre_render();
+
+        assert!(!app.ui_config.show_largest);
+        assert_eq!(*app.store.get_current_path(), root.join("docs"));
+        let folder = get_current_folder(&app).unwrap();
+        assert_eq!(folder.get_selected_entry().title, "notes.txt");
+    }
+
+    #[test]
+    fn deletes_selected_file() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        app.on_toggle_largest();
+        app.on_delete();
+        assert!(app.ui_config.confirming_deletion);
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(!PathBuf::from(format!("{path}/project/src/main.rs")).exists());
+        assert!(app.ui_config.show_largest);
+        assert_eq!(app.task_manager.largest_files.len(), 3);
+        assert_eq!(
+            app.selected_largest_file().map(|(_, size)| size),
+            Some(TEST_FILE_SIZE * 3)
+        );
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 6
+        );
+    }
+
+    #[test]
+    fn is_cleared_on_rescan() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        fs::remove_file(format!("{path}/root.txt")).unwrap();
+        app.reset();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.task_manager.largest_files.len(), 3);
+    }
+
+    #[test]
+    fn does_not_act_on_hidden_folder() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+        let root = app.store.get_current_path().clone();
+
+        app.on_toggle_largest();
+        app.on_toggle_mark();
+        app.on_mark_all();
+        app.on_invert_marks();
+        app.on_toggle_sorting();
+
+        assert!(get_current_folder(&app)
+            .unwrap()
+            .get_marked_entries()
+            .is_empty());
+        assert_eq!(app.ui_config.sort_by, SortBy::Title);
+
+        app.on_backspace();
+        assert!(!app.ui_config.show_largest);
+        assert_eq!(*app.store.get_current_path(), root);
+    }
+}
@@ -0,0 +1,215 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+use wiper::fs::FolderEntryType;
+
+mod multi_select {
+
+    use wiper::fs::{DataStore, DataStoreType};
+
+    use super::*;
+    use std::path::PathBuf;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - folder_1
+    ///     - folder_2
+    ///         - folder_3
+    ///         - file_1 .. file_3
+    ///     - file_1 .. file_3
+    /// - file_1 .. file_3
+    const FOLDERS: &[&str] = &["folder_1/folder_2/folder_3"];
+
+    fn testing_files() -> Vec<(String, u64)> {
+        let mut files = vec![];
+        let mut folder_path = String::new();
+        for folder_index in 1..4 {
+            for file_index in 1..4 {
+                files.push((
+                    format!("{folder_path}file_{file_index}.txt"),
+                    TEST_FILE_SIZE,
+                ));
+            }
+            folder_path = format!("{folder_path}folder_{folder_index}/");
+        }
+        files
+    }
+
+    fn marked_titles(app: &App<DataStoreType>) -> Vec<String> {
+        get_current_folder(app)
+            .unwrap()
+            .get_marked_entries()
+            .iter()
+            .map(|e| e.title.clone())
+            .collect()
+    }
+
+    fn absolute(path: &str) -> PathBuf {
+        std::env::current_dir().unwrap().join(path)
+    }
+
+    #[test]
+    fn toggles_mark_and_moves_cursor_down() {
+        let path = create_testing_files(FOLDERS, &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        assert_cursor_index(&app, 2);
+        assert_eq!(marked_titles(&app), vec!["folder_1"]);
+
+        app.on_cursor_up();
+        app.on_toggle_mark();
+        assert!(marked_titles(&app).is_empty());
+    }
+
+    #[test]
+    fn never_marks_parent_entry() {
+        let path = create_testing_files(FOLDERS, &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        app.on_toggle_mark();
+        assert!(marked_titles(&app).is_empty());
+
+        app.on_mark_all();
+        assert_eq!(marked_titles(&app).len(), 4);
+        assert!(!get_current_folder(&app).unwrap().entries[0].is_marked);
+    }
+
+    #[test]
+    fn inverts_marks() {
+        let path = create_testing_files(FOLDERS, &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        app.on_invert_marks();
+        assert_eq!(
+            marked_titles(&app),
+            vec!["file_1.txt", "file_2.txt", "file_3.txt"]
+        );
+    }
+
+    #[test]
+    fn sums_size_of_marked_entries() {
+        let path = create_testing_files(FOLDERS, &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        app.on_toggle_mark();
+        let folder = get_current_folder(&app).unwrap();
+        assert_eq!(folder.get_marked_size(), TEST_FILE_SIZE * 7);
+
+        app.on_mark_all();
+        let folder = get_current_folder(&app).unwrap();
+        assert_eq!(folder.get_marked_size(), folder.get_size());
+    }
+
+    #[test]
+    fn deletes_marked_entries_with_one_confirmation() {
+        let path = create_testing_files(FOLDERS, &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        app.on_toggle_mark();
+        app.on_delete();
+        assert!(app.ui_config.confirming_deletion);
+        assert_eq!(marked_titles(&app).len(), 2);
+
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(!app.ui_config.confirming_deletion);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::Folder).len(), 0);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 2);
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 2
+        );
+        assert!(!PathBuf::from(format!("{path}/folder_1")).exists());
+        assert!(!PathBuf::from(format!("{path}/file_1.txt")).exists());
+
+        // Records of deleted folder and everything inside are dropped
+        let folder_path = absolute(&format!("{path}/folder_1"));
+        assert!(app
+            .store
+            .get_keys()
+            .iter()
+            .all(|key| !key.starts_with(&folder_path)));
+    }
+
+    #[test]
+    fn deletes_entry_under_cursor_without_marks() {
+        let path = create_testing_files(FOLDERS, &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::Folder).len(), 1);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 2);
+    }
+
+    #[test]
+    fn deleting_marked_entries_updates_parent_folders_sizes() {
+        let path = create_testing_files(FOLDERS, &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 9
+        );
+
+        // Cursor is moved away in every folder, sizes must not depend on it
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+
+        app.on_mark_all();
+        app.on_cursor_up();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        let folder_2 = get_current_folder(&app).unwrap();
+        assert_eq!(folder_2.entries.len(), 1);
+        assert_eq!(folder_2.get_size(), 0);
+
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+        let folder_1 = get_current_folder(&app).unwrap();
+        assert_eq!(folder_1.get_size(), TEST_FILE_SIZE * 3);
+        assert_eq!(get_entry_by_title(&app, "folder_2").size, Some(0));
+
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+        let root = get_current_folder(&app).unwrap();
+        assert_eq!(root.get_size(), TEST_FILE_SIZE * 6);
+        assert_eq!(
+            get_entry_by_title(&app, "folder_1").size,
+            Some(TEST_FILE_SIZE * 3)
+        );
+    }
+
+    #[test]
+    fn resets_confirmation_on_mark_change() {
+        let path = create_testing_files(FOLDERS, &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        app.on_mark_all();
+        app.on_delete();
+        app.on_invert_marks();
+        assert!(!app.ui_config.confirming_deletion);
+
+        // Nothing is marked and cursor is at parent entry
+        app.on_delete();
+        assert!(!app.ui_config.confirming_deletion);
+    }
+}
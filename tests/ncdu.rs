@@ -0,0 +1,220 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod ncdu {
+
+    use wiper::config::InitConfig;
+    use wiper::export::{read_ncdu, write_ncdu};
+    use wiper::fs::{DataStore, DataStoreType, FolderEntryType, SizeMode, SortBy};
+    use wiper::logger::MessageLevel;
+
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::process::Command;
+
+    /// - docs (could not be read)
+    ///     - notes.txt (10/4096)
+    ///     - report.pdf (20/4096)
+    /// - cache (excluded)
+    /// - link_a.bin (500/512, hard link)
+    /// - link_b.bin (500/512, hard link to the same inode)
+    /// - broken.txt (could not be read)
+    const DUMP: &str = r#"[1, 2, {"progname": "ncdu", "progver": "1.19", "timestamp": 1700000000},
+        [{"name": "/srv/dump"},
+            [{"name": "docs", "read_error": true},
+                {"name": "notes.txt", "asize": 10, "dsize": 4096},
+                {"name": "report.pdf", "asize": 20, "dsize": 4096}],
+            {"name": "cache", "excluded": "pattern"},
+            {"name": "link_a.bin", "asize": 500, "dsize": 512, "dev": 1, "ino": 42, "hlnkc": true},
+            {"name": "link_b.bin", "asize": 500, "dsize": 512, "dev": 1, "ino": 42, "hlnkc": true},
+            {"name": "broken.txt", "read_error": true}]]"#;
+
+    fn write_dump(postfix: &str, content: &str) -> String {
+        fs::create_dir_all(TEST_FILE_PATH_EDIT.rsplit_once('/').unwrap().0)
+            .expect("Failed to create test folder");
+        let dump_path = format!("{TEST_FILE_PATH_EDIT}_ncdu_{postfix}.json");
+        fs::write(&dump_path, content).expect("Failed to write dump");
+        dump_path
+    }
+
+    fn cleanup_dump(dump_path: &str) {
+        if let Err(err) = fs::remove_file(dump_path) {
+            eprintln!("Failed to remove dump: {err}");
+        }
+    }
+
+    fn setup_app_import(dump_path: &str, size_mode: SizeMode) -> App<DataStoreType> {
+        let mut app: App<DataStoreType> = App::new(InitConfig::default()).unwrap();
+        app.ui_config.size_mode = size_mode;
+        app.ui_config.sort_by = SortBy::Title;
+        app.import(&PathBuf::from(dump_path)).unwrap();
+        app
+    }
+
+    fn has_error_message(app: &App<DataStoreType>, text: &str) -> bool {
+        app.logger.messages.iter().any(|(_, level, message)| {
+            matches!(level, MessageLevel::Error) && message.contains(text)
+        })
+    }
+
+    #[test]
+    fn writes_scan_as_ncdu_dump() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let root = app.store.get_current_path().clone();
+        let mut output = vec![];
+        write_ncdu(&app.store, &root, &SizeMode::Apparent, &mut output).unwrap();
+
+        let dump: serde_json::Value = serde_json::from_slice(&output).unwrap();
+        assert_eq!(dump[0], 1);
+        assert_eq!(dump[2]["progname"], "wiper");
+        let root_folder = dump[3].as_array().unwrap();
+        assert_eq!(root_folder[0]["name"], root.to_string_lossy().as_ref());
+        assert_eq!(root_folder.len(), 7);
+        assert_eq!(root_folder[1][0]["name"], "a_folder");
+        assert_eq!(root_folder[4]["name"], "a_root_file.txt");
+        assert_eq!(
+            root_folder[4]["asize"],
+            get_entry_by_title(&app, "a_root_file.txt").size.unwrap()
+        );
+        assert!(root_folder[4].get("dsize").is_none());
+    }
+
+    #[test]
+    fn reads_written_dump_back() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let root = app.store.get_current_path().clone();
+        let mut output = vec![];
+        write_ncdu(&app.store, &root, &SizeMode::Apparent, &mut output).unwrap();
+
+        let mut store = DataStoreType::new();
+        let imported_root = read_ncdu(output.as_slice(), &SizeMode::Apparent, &mut store).unwrap();
+
+        assert_eq!(imported_root, root);
+        let scanned = app.store.get_folder(&root).unwrap();
+        let imported = store.get_folder(&root).unwrap();
+        assert_eq!(imported.get_size(), scanned.get_size());
+        assert_eq!(imported.file_type_map, scanned.file_type_map);
+        assert_eq!(
+            store.get_folder(&root.join("b_folder")).unwrap().get_size(),
+            get_entry_by_title(&app, "b_folder").size.unwrap()
+        );
+    }
+
+    #[test]
+    fn imports_dump_in_selected_size_mode() {
+        let dump_path = write_dump("01", DUMP);
+        let mut app = setup_app_import(&dump_path, SizeMode::Apparent);
+
+        assert!(app.ui_config.read_only);
+        assert_eq!(app.store.get_current_path(), &PathBuf::from("/srv/dump"));
+        let root = get_current_folder(&app).unwrap();
+        // Second hard link is not counted
+        assert_eq!(root.get_size(), 10 + 20 + 500);
+        assert_eq!(root.hard_links, 1);
+        assert_eq!(root.file_type_map["bin"], 500);
+        assert_eq!(root.file_type_map["txt"], 10);
+
+        let cache = get_entry_by_title(&app, "cache");
+        assert_eq!(cache.kind, FolderEntryType::Folder);
+        assert!(cache.is_excluded);
+        assert!(get_entry_by_title(&app, "broken.txt").has_error);
+
+        let docs = app
+            .store
+            .get_folder(&PathBuf::from("/srv/dump/docs"))
+            .unwrap();
+        assert!(docs.has_error);
+        assert_eq!(app.store.get_errors().len(), 1);
+        assert_eq!(
+            app.store.get_errors()[0].path,
+            PathBuf::from("/srv/dump/docs")
+        );
+
+        let app = setup_app_import(&dump_path, SizeMode::Allocated);
+        assert_eq!(get_current_folder(&app).unwrap().get_size(), 4096 * 2 + 512);
+        cleanup_dump(&dump_path);
+    }
+
+    #[test]
+    fn refuses_to_touch_filesystem_in_imported_session() {
+        let dump_path = write_dump("02", DUMP);
+        let mut app = setup_app_import(&dump_path, SizeMode::Apparent);
+
+        app.on_cursor_down();
+        app.on_delete();
+        assert!(!app.ui_config.confirming_deletion);
+        assert!(has_error_message(&app, "deletion is disabled"));
+
+        app.reset();
+        assert_eq!(get_current_folder(&app).unwrap().get_size(), 530);
+
+        app.on_toggle_size_mode();
+        assert_eq!(app.ui_config.size_mode, SizeMode::Apparent);
+
+        app.on_backspace();
+        assert_eq!(app.store.get_current_path(), &PathBuf::from("/srv/dump"));
+        assert!(has_error_message(&app, "leaving the dump root is disabled"));
+
+        app.on_enter();
+        assert_eq!(
+            app.store.get_current_path(),
+            &PathBuf::from("/srv/dump/docs")
+        );
+        assert!(get_current_folder(&app).is_some());
+        app.on_backspace();
+        assert_eq!(app.store.get_current_path(), &PathBuf::from("/srv/dump"));
+        cleanup_dump(&dump_path);
+    }
+
+    #[test]
+    fn rejects_invalid_dump() {
+        let mut store = DataStoreType::new();
+        for dump in ["{}", "[2, 0, {}, [{\"name\": \"/\"}]]", "[1, 2, {}, [{}]]"] {
+            let err = read_ncdu(dump.as_bytes(), &SizeMode::Apparent, &mut store).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        }
+
+        let output = Command::new(env!("CARGO_BIN_EXE_wiper"))
+            .args(["--import", "/nonexistent/dump.json", "--export", "json"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(1));
+        assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to import"));
+    }
+
+    #[test]
+    fn binary_converts_between_formats() {
+        let dump_path = write_dump("03", DUMP);
+        let output = Command::new(env!("CARGO_BIN_EXE_wiper"))
+            .args(["--import", &dump_path, "--export", "json"])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json["root"]["path"], "/srv/dump");
+        assert_eq!(json["root"]["size"], 530);
+
+        let output_path = format!("{TEST_FILE_PATH_EDIT}_ncdu_03_view.json");
+        let output = Command::new(env!("CARGO_BIN_EXE_wiper"))
+            .args(["--export", "ncdu", "-o", &output_path, TEST_FILE_PATH_VIEW])
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let mut store = DataStoreType::new();
+        let root = read_ncdu(
+            fs::File::open(&output_path).unwrap(),
+            &SizeMode::Apparent,
+            &mut store,
+        )
+        .unwrap();
+        assert!(root.ends_with("view"));
+        assert!(store.get_folder(&root.join("a_folder")).is_some());
+
+        cleanup_dump(&output_path);
+        cleanup_dump(&dump_path);
+    }
+}
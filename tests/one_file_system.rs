@@ -0,0 +1,102 @@
+#![cfg(unix)]
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+use wiper::config::InitConfig;
+mod one_file_system {
+
+    use wiper::fs::{DataStore, DataStoreType};
+
+    use super::*;
+    use std::os::unix::fs::MetadataExt;
+    use std::path::{Path, PathBuf};
+
+    /// Looks for a small folder which contains a mount point, e.g. `/dev` and `/dev/pts`
+    fn find_mount_point() -> Option<(PathBuf, String)> {
+        ["/dev/pts", "/dev/shm", "/dev/mqueue"]
+            .iter()
+            .map(Path::new)
+            .find(|mount_point| {
+                let parent = mount_point.parent().unwrap();
+                match (mount_point.metadata(), parent.metadata()) {
+                    (Ok(mount), Ok(parent)) => mount.dev() != parent.dev(),
+                    _ => false,
+                }
+            })
+            .map(|mount_point| {
+                (
+                    mount_point.parent().unwrap().to_path_buf(),
+                    mount_point
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string(),
+                )
+            })
+    }
+
+    fn setup_app(path: &str, one_file_system: bool) -> App<DataStoreType> {
+        setup_app_with(InitConfig {
+            file_path: Some(path.to_string()),
+            one_file_system: Some(one_file_system),
+            ..InitConfig::default()
+        })
+    }
+
+    #[test]
+    fn keeps_totals_on_single_filesystem() {
+        let app = setup_app(TEST_FILE_PATH_VIEW, true);
+        let app_all = setup_app(TEST_FILE_PATH_VIEW, false);
+
+        assert_root_view_folder_sorted_by_title(&app);
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            get_current_folder(&app_all).unwrap().get_size()
+        );
+        assert_eq!(app.store.get_nodes_len(), app_all.store.get_nodes_len());
+        assert!(get_current_folder(&app)
+            .unwrap()
+            .entries
+            .iter()
+            .all(|entry| !entry.is_mount_point));
+    }
+
+    #[test]
+    fn marks_and_skips_mount_points() {
+        let Some((parent, mount_point)) = find_mount_point() else {
+            eprintln!("No mount point found, skipping");
+            return;
+        };
+        let mut app = setup_app(&parent.to_string_lossy(), true);
+
+        let entry = get_entry_by_title(&app, &mount_point);
+        assert!(entry.is_mount_point);
+        assert_eq!(entry.size, Some(0));
+
+        let mount_point_path = parent.join(&mount_point);
+        assert!(app
+            .store
+            .get_keys()
+            .iter()
+            .all(|key| !key.starts_with(&mount_point_path) || key == &mount_point_path));
+        assert_eq!(
+            app.store
+                .get_folder_mut(&mount_point_path)
+                .unwrap()
+                .entries
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn marks_but_traverses_mount_points_by_default() {
+        let Some((parent, mount_point)) = find_mount_point() else {
+            eprintln!("No mount point found, skipping");
+            return;
+        };
+        let app = setup_app(&parent.to_string_lossy(), false);
+
+        assert!(get_entry_by_title(&app, &mount_point).is_mount_point);
+    }
+}
@@ -0,0 +1,104 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod progress {
+
+    use wiper::config::InitConfig;
+    use wiper::fs::DataStoreType;
+    use wiper::task_manager::ScanProgress;
+
+    use super::*;
+
+    const TEST_FILE_SIZE: u64 = 100;
+    const FOLDERS: u64 = 3;
+    const FILES_PER_FOLDER: u64 = 4;
+    const ROOT_FILES: u64 = 2;
+
+    /// - folder_0 .. folder_2
+    ///     - file_0.txt .. file_3.txt (100 bytes each)
+    /// - file_0.txt .. file_1.txt (100 bytes each)
+    fn testing_files() -> Vec<(String, u64)> {
+        let mut files = vec![];
+        for folder in 0..FOLDERS {
+            for file in 0..FILES_PER_FOLDER {
+                files.push((format!("folder_{folder}/file_{file}.txt"), TEST_FILE_SIZE));
+            }
+        }
+        for file in 0..ROOT_FILES {
+            files.push((format!("file_{file}.txt"), TEST_FILE_SIZE));
+        }
+        files
+    }
+
+    fn expected_progress() -> ScanProgress {
+        let files = FOLDERS * FILES_PER_FOLDER + ROOT_FILES;
+        ScanProgress {
+            files,
+            // Root folder is counted as well
+            directories: FOLDERS + 1,
+            bytes: files * TEST_FILE_SIZE,
+            directories_pending: 0,
+            errors: 0,
+        }
+    }
+
+    #[test]
+    fn counts_entries_and_bytes() {
+        let path = create_testing_files(&[], &testing_files());
+        let app: App<DataStoreType> = setup_app_with(path.config());
+
+        let stats = &app.task_manager.stats;
+        assert_eq!(stats.total(), expected_progress());
+        assert!(stats.elapsed().as_nanos() > 0);
+        assert_eq!(stats.completion(), None);
+        assert_eq!(stats.estimated_remaining(), None);
+    }
+
+    #[test]
+    fn estimates_completion_of_rescan() {
+        let path = create_testing_files(&[], &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        app.reset();
+        assert_eq!(app.task_manager.stats.total().entries(), 0);
+        assert_eq!(app.task_manager.stats.completion(), Some(0.0));
+
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(app.task_manager.stats.total(), expected_progress());
+        assert_eq!(app.task_manager.stats.completion(), Some(1.0));
+    }
+
+    #[test]
+    fn keeps_counters_after_reset_mid_scan() {
+        let path = create_testing_files(&[], &testing_files());
+        let mut app: App<DataStoreType> = start_app_with(path.config());
+
+        app.tick();
+        app.reset();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.task_manager.stats.total(), expected_progress());
+    }
+
+    #[test]
+    fn restarts_counters_on_navigation_after_finished_scan() {
+        let path = create_testing_files(&[], &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(InitConfig {
+            file_path: Some(format!("{path}/folder_0")),
+            ..InitConfig::default()
+        });
+
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+
+        // Only folders other than the already scanned one are walked
+        let total = app.task_manager.stats.total();
+        assert_eq!(total.files, (FOLDERS - 1) * FILES_PER_FOLDER);
+        assert_eq!(
+            total.bytes,
+            (FOLDERS - 1) * FILES_PER_FOLDER * TEST_FILE_SIZE
+        );
+        assert_eq!(total.directories_pending, 0);
+    }
+}
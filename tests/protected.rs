@@ -0,0 +1,172 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+use wiper::config::InitConfig;
+
+mod protected {
+
+    use wiper::fs::{DataStore, DataStoreType, FolderEntryType, ProtectFilter};
+
+    use super::*;
+    use std::fs;
+    use std::io::ErrorKind;
+    use std::path::{Path, PathBuf};
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - project
+    ///     - .ssh
+    ///         - id_rsa
+    /// - id.key
+    /// - file.txt
+    const FILES: &[(&str, u64)] = &[
+        ("project/.ssh/id_rsa", TEST_FILE_SIZE),
+        ("id.key", TEST_FILE_SIZE),
+        ("file.txt", TEST_FILE_SIZE),
+    ];
+
+    fn absolute(path: &str) -> PathBuf {
+        std::env::current_dir().unwrap().join(path)
+    }
+
+    fn setup_app(path: String, protect: &[String]) -> App<DataStoreType> {
+        setup_app_with(InitConfig {
+            file_path: Some(path),
+            protect: protect.to_vec(),
+            ..InitConfig::default()
+        })
+    }
+
+    fn select_entry(app: &mut App<DataStoreType>, title: &str) {
+        while get_current_folder(app).unwrap().get_selected_entry().title != title {
+            app.on_cursor_down();
+        }
+    }
+
+    #[test]
+    fn protects_default_paths() {
+        let home = Path::new("/home/user");
+        let filter = ProtectFilter::new(&[], Some(home)).unwrap();
+
+        for path in ["/", "/etc", "/usr", "/home/user", "/home"] {
+            assert!(filter.is_protected(Path::new(path)), "{path}");
+        }
+        for path in ["/usr/local/tmp", "/home/user/Downloads", "/tmp"] {
+            assert!(!filter.is_protected(Path::new(path)), "{path}");
+        }
+    }
+
+    #[test]
+    fn protects_user_patterns() {
+        let home = Path::new("/home/user");
+        let patterns = vec!["~/.ssh".to_string(), "*.key".to_string()];
+        let filter = ProtectFilter::new(&patterns, Some(home)).unwrap();
+
+        assert!(filter.is_protected(Path::new("/home/user/.ssh")));
+        assert!(filter.is_protected(Path::new("/tmp/id.key")));
+        assert!(!filter.is_protected(Path::new("/tmp/.ssh")));
+        assert!(!filter.is_protected(Path::new("/home/user/.ssh/known_hosts")));
+    }
+
+    #[test]
+    fn returns_error_for_invalid_pattern() {
+        let c = InitConfig {
+            protect: vec!["[".to_string()],
+            ..InitConfig::default()
+        };
+        assert!(App::<DataStoreType>::new(c).is_err());
+    }
+
+    #[test]
+    fn refuses_to_delete_protected_entry() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app(path.to_string(), &["*.key".to_string()]);
+
+        select_entry(&mut app, "id.key");
+        assert!(get_entry_by_title(&app, "id.key").is_protected);
+        app.on_delete();
+
+        assert!(!app.ui_config.confirming_deletion);
+        assert_eq!(app.delete_failures.len(), 1);
+        assert_eq!(app.delete_failures[0].kind, ErrorKind::PermissionDenied);
+        assert!(!app.delete_failures[0].in_trash);
+
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+        assert!(Path::new(&format!("{path}/id.key")).exists());
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 2);
+    }
+
+    #[test]
+    fn refuses_folder_containing_protected_path() {
+        let path = create_testing_files(&[], FILES);
+        let protected = absolute(&format!("{path}/project/.ssh"));
+        let mut app = setup_app(path.to_string(), &[protected.to_string_lossy().to_string()]);
+
+        select_entry(&mut app, "project");
+        assert!(get_entry_by_title(&app, "project").is_protected);
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(protected.exists());
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::Folder).len(), 1);
+    }
+
+    #[test]
+    fn refuses_whole_batch_with_protected_entry() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app(path.to_string(), &["*.key".to_string()]);
+
+        select_entry(&mut app, "file.txt");
+        app.on_toggle_mark();
+        select_entry(&mut app, "id.key");
+        app.on_toggle_mark();
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(app.delete_failures.len(), 1);
+        assert!(app.delete_failures[0].task.path().ends_with("id.key"));
+        assert!(Path::new(&format!("{path}/file.txt")).exists());
+        assert!(Path::new(&format!("{path}/id.key")).exists());
+    }
+
+    #[test]
+    fn deletes_unprotected_entry() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app(path.to_string(), &["*.key".to_string()]);
+
+        select_entry(&mut app, "file.txt");
+        assert!(!get_entry_by_title(&app, "file.txt").is_protected);
+        app.on_delete();
+        assert!(app.ui_config.confirming_deletion);
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(app.delete_failures.is_empty());
+        assert!(!Path::new(&format!("{path}/file.txt")).exists());
+    }
+
+    #[test]
+    fn refuses_home_scanned_through_parent_path() {
+        let path = create_testing_files(&[], FILES);
+        for folder in ["home/user", "home/other"] {
+            fs::create_dir_all(format!("{path}/{folder}")).expect("Failed to create test folder");
+        }
+        // Only this test reads home folder of the app
+        let home = fs::canonicalize(format!("{path}/home/user")).unwrap();
+        std::env::set_var("HOME", &home);
+        let mut app = setup_app(format!("{path}/home/other/.."), &[]);
+
+        assert_eq!(*app.store.get_current_path(), home.parent().unwrap());
+        select_entry(&mut app, "user");
+        assert!(get_entry_by_title(&app, "user").is_protected);
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(home.exists());
+        assert_eq!(app.delete_failures.len(), 1);
+    }
+}
@@ -0,0 +1,151 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod refresh {
+
+    use wiper::config::InitConfig;
+    use wiper::fs::{DataStore, DataStoreType};
+    use wiper::logger::MessageLevel;
+
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - docs
+    ///     - notes.txt
+    /// - project
+    ///     - src
+    ///         - main.rs
+    ///     - README.md
+    /// - root.txt
+    const FILES: &[(&str, u64)] = &[
+        ("docs/notes.txt", TEST_FILE_SIZE),
+        ("project/src/main.rs", TEST_FILE_SIZE),
+        ("project/README.md", TEST_FILE_SIZE),
+        ("root.txt", TEST_FILE_SIZE),
+    ];
+
+    fn folder_path(app: &mut App<DataStoreType>, relative_path: &str) -> PathBuf {
+        app.store.get_current_path().join(relative_path)
+    }
+
+    #[test]
+    fn refreshes_current_folder_and_its_ancestors() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+        let docs = folder_path(&mut app, "docs");
+        app.store.get_folder_mut(&docs).unwrap().cursor_index = 1;
+
+        // Enter project
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        write_file(&path, "project/src/lib.rs", TEST_FILE_SIZE * 2);
+        fs::remove_file(format!("{path}/project/README.md")).unwrap();
+
+        app.on_refresh_current();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 3
+        );
+        assert_eq!(
+            get_entry_by_title(&app, "src").size,
+            Some(TEST_FILE_SIZE * 3)
+        );
+        assert!(get_current_folder(&app)
+            .unwrap()
+            .entries
+            .iter()
+            .all(|entry| entry.title != "README.md"));
+
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+        let root = get_current_folder(&app).unwrap();
+        assert_eq!(root.get_size(), TEST_FILE_SIZE * 5);
+        assert_eq!(root.file_type_map["rs"], TEST_FILE_SIZE * 3);
+        assert!(!root.file_type_map.contains_key("md"));
+        assert_eq!(root.cursor_index, 2);
+        assert_item_at_index_title(&app, 2, "project".into());
+        assert_eq!(
+            get_entry_by_title(&app, "project").size,
+            Some(TEST_FILE_SIZE * 3)
+        );
+        // Other folders are not scanned again
+        assert_eq!(app.store.get_folder(&docs).unwrap().cursor_index, 1);
+    }
+
+    #[test]
+    fn rescans_everything_at_root() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        write_file(&path, "docs/todo.txt", TEST_FILE_SIZE);
+        app.on_refresh_current();
+        handle_tasks_synchronously(&mut app);
+
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 5
+        );
+    }
+
+    #[test]
+    fn waits_for_scan_to_finish() {
+        let path = create_testing_files(&[], FILES);
+        let c = InitConfig {
+            file_path: Some(path.to_string()),
+            ..InitConfig::default()
+        };
+        let mut app: App<DataStoreType> = App::new(c).unwrap();
+        app.init();
+
+        app.on_refresh_current();
+
+        assert!(app.logger.messages.iter().any(|(_, level, message)| {
+            matches!(level, MessageLevel::Error) && message.contains("Refresh is disabled")
+        }));
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 4
+        );
+    }
+
+    #[test]
+    fn counts_hard_links_of_refreshed_folder_again() {
+        let path = create_testing_files(&[], FILES);
+        fs::hard_link(
+            format!("{path}/project/README.md"),
+            format!("{path}/project/src/README.md"),
+        )
+        .expect("Failed to create hard link");
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+        // Enter project
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 2
+        );
+
+        app.on_refresh_current();
+        handle_tasks_synchronously(&mut app);
+
+        let project = get_current_folder(&app).unwrap();
+        assert_eq!(project.get_size(), TEST_FILE_SIZE * 2);
+        assert_eq!(project.hard_links, 1);
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+        let root = get_current_folder(&app).unwrap();
+        assert_eq!(root.get_size(), TEST_FILE_SIZE * 4);
+        assert_eq!(root.hard_links, 1);
+    }
+}
@@ -0,0 +1,95 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod reset {
+
+    use wiper::fs::DataStoreType;
+    use wiper::task_manager::TraversalEvent;
+
+    use super::*;
+    use std::thread;
+
+    const TEST_FILE_SIZE: u64 = 100;
+    const FOLDERS: u64 = 20;
+    const FILES_PER_FOLDER: u64 = 20;
+    const TOTAL_SIZE: u64 = TEST_FILE_SIZE * FOLDERS * FILES_PER_FOLDER;
+
+    /// - folder_0 .. folder_19
+    ///     - file_0.txt .. file_19.txt (100 bytes each)
+    fn testing_files() -> Vec<(String, u64)> {
+        let mut files = vec![];
+        for folder in 0..FOLDERS {
+            for file in 0..FILES_PER_FOLDER {
+                files.push((format!("folder_{folder}/file_{file}.txt"), TEST_FILE_SIZE));
+            }
+        }
+        files
+    }
+
+    fn assert_total_size(app: &App<DataStoreType>) {
+        let folder = get_current_folder(app).unwrap();
+        assert_eq!(folder.get_size(), TOTAL_SIZE);
+        assert_eq!(folder.entries.len() as u64, FOLDERS + 1);
+    }
+
+    #[test]
+    fn keeps_totals_after_reset_before_first_tick() {
+        let path = create_testing_files(&[], &testing_files());
+        let mut app: App<DataStoreType> = start_app_with(path.config());
+
+        app.reset();
+        handle_tasks_synchronously(&mut app);
+
+        assert_total_size(&app);
+    }
+
+    #[test]
+    fn keeps_totals_after_reset_mid_scan() {
+        let path = create_testing_files(&[], &testing_files());
+        let mut app: App<DataStoreType> = start_app_with(path.config());
+
+        app.tick();
+        app.reset();
+        app.tick();
+        app.reset();
+        handle_tasks_synchronously(&mut app);
+
+        assert_total_size(&app);
+    }
+
+    #[test]
+    fn keeps_totals_after_reset_of_finished_scan() {
+        let path = create_testing_files(&[], &testing_files());
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        app.reset();
+        handle_tasks_synchronously(&mut app);
+
+        assert_total_size(&app);
+    }
+
+    #[test]
+    fn ignores_events_of_previous_generation() {
+        let path = create_testing_files(&[], &testing_files());
+        let mut app: App<DataStoreType> = start_app_with(path.config());
+        let stale_generation = app.task_manager.generation;
+
+        app.reset();
+        assert_ne!(app.task_manager.generation, stale_generation);
+
+        // Channel may be full, so stale event is sent while the app drains it
+        let event_tx = app.task_manager.event_tx.clone();
+        let sender = thread::spawn(move || {
+            event_tx
+                .send(TraversalEvent::Finished(stale_generation))
+                .unwrap();
+        });
+        handle_tasks_synchronously(&mut app);
+        sender.join().unwrap();
+        app.tick();
+
+        assert!(app.task_manager.is_done());
+        assert_total_size(&app);
+    }
+}
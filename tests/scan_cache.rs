@@ -0,0 +1,209 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod scan_cache {
+
+    use wiper::config::InitConfig;
+    use wiper::fs::{DataStore, DataStoreType, SizeMode, SortBy};
+    use wiper::scan_cache::{cache_file_path, ScanCache};
+
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+    use std::thread;
+    use std::time::{Duration, Instant};
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - scan
+    ///     - docs
+    ///         - notes.txt
+    ///     - src
+    ///         - main.rs
+    ///     - root.txt
+    const FILES: &[(&str, u64)] = &[
+        ("scan/docs/notes.txt", TEST_FILE_SIZE),
+        ("scan/src/main.rs", TEST_FILE_SIZE),
+        ("scan/root.txt", TEST_FILE_SIZE),
+    ];
+
+    fn setup_app(path: &str, config: InitConfig) -> App<DataStoreType> {
+        let c = InitConfig {
+            file_path: Some(format!("{path}/scan")),
+            cache: config.cache.or(Some(true)),
+            cache_dir: Some(PathBuf::from(format!("{path}/cache"))),
+            ..config
+        };
+        let mut app: App<DataStoreType> = App::new(c).unwrap();
+        app.ui_config.open_file = false;
+        app.ui_config.sort_by = SortBy::Title;
+        app
+    }
+
+    fn scan_and_save(path: &str) {
+        let mut app = setup_app(path, InitConfig::default());
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        app.save_cache().unwrap();
+    }
+
+    fn wait_for_validation(app: &mut App<DataStoreType>) {
+        let started_at = Instant::now();
+        while app.cache_validator.is_some() || !app.task_manager.is_done() {
+            assert!(started_at.elapsed() < Duration::from_secs(5));
+            app.tick();
+            thread::sleep(Duration::from_millis(10));
+        }
+        app.pre_render();
+    }
+
+    fn folder_size(app: &mut App<DataStoreType>, relative_path: &str) -> u64 {
+        let path = app.store.get_current_path().join(relative_path);
+        app.store.get_folder(&path).unwrap().get_size()
+    }
+
+    #[test]
+    fn loads_cached_tree_as_stale() {
+        let path = create_testing_files(&[], FILES);
+        scan_and_save(&path);
+        fs::remove_dir_all(format!("{path}/scan/docs")).unwrap();
+
+        let mut app = setup_app(&path, InitConfig::default());
+        assert!(app.load_cache());
+        app.pre_render();
+
+        assert!(app.task_manager.is_done());
+        assert_eq!(folder_size(&mut app, ""), TEST_FILE_SIZE * 3);
+        assert_eq!(folder_size(&mut app, "docs"), TEST_FILE_SIZE);
+        assert_item_at_index_title(&app, 1, "docs".into());
+        assert!(get_entry_by_title(&app, "docs").is_stale);
+        assert!(get_entry_by_title(&app, "root.txt").is_stale);
+        assert_eq!(
+            get_current_folder(&app).unwrap().file_type_map["rs"],
+            TEST_FILE_SIZE
+        );
+    }
+
+    #[test]
+    fn revalidates_modified_folders() {
+        let path = create_testing_files(&[], FILES);
+        scan_and_save(&path);
+        write_file(&path, "scan/src/lib.rs", TEST_FILE_SIZE * 2);
+        fs::remove_file(format!("{path}/scan/docs/notes.txt")).unwrap();
+
+        let mut app = setup_app(&path, InitConfig::default());
+        assert!(app.load_cache());
+        wait_for_validation(&mut app);
+
+        assert_eq!(folder_size(&mut app, "src"), TEST_FILE_SIZE * 3);
+        assert_eq!(folder_size(&mut app, "docs"), 0);
+        assert_eq!(folder_size(&mut app, ""), TEST_FILE_SIZE * 4);
+        assert_eq!(
+            get_entry_by_title(&app, "src").size,
+            Some(TEST_FILE_SIZE * 3)
+        );
+        assert!(get_current_folder(&app)
+            .unwrap()
+            .entries
+            .iter()
+            .all(|entry| !entry.is_stale));
+    }
+
+    #[test]
+    fn scans_new_folders_while_revalidating() {
+        let path = create_testing_files(&[], FILES);
+        scan_and_save(&path);
+        fs::create_dir_all(format!("{path}/scan/assets/images")).unwrap();
+        write_file(&path, "scan/assets/images/logo.png", TEST_FILE_SIZE * 4);
+
+        let mut app = setup_app(&path, InitConfig::default());
+        assert!(app.load_cache());
+        wait_for_validation(&mut app);
+
+        assert_eq!(folder_size(&mut app, "assets"), TEST_FILE_SIZE * 4);
+        assert_eq!(folder_size(&mut app, ""), TEST_FILE_SIZE * 7);
+
+        // Saved again with the new folder
+        app.save_cache().unwrap();
+        let mut app = setup_app(&path, InitConfig::default());
+        assert!(app.load_cache());
+        assert_eq!(folder_size(&mut app, "assets/images"), TEST_FILE_SIZE * 4);
+    }
+
+    #[test]
+    fn skips_cache_made_with_other_options() {
+        let path = create_testing_files(&[], FILES);
+        scan_and_save(&path);
+
+        let mut app = setup_app(
+            &path,
+            InitConfig {
+                size_mode: Some(SizeMode::Allocated),
+                ..InitConfig::default()
+            },
+        );
+        assert!(!app.load_cache());
+        let mut app = setup_app(
+            &path,
+            InitConfig {
+                exclude: vec!["docs".into()],
+                ..InitConfig::default()
+            },
+        );
+        assert!(!app.load_cache());
+        let mut app = setup_app(
+            &path,
+            InitConfig {
+                cache: Some(false),
+                ..InitConfig::default()
+            },
+        );
+        assert!(!app.load_cache());
+    }
+
+    #[test]
+    fn does_not_save_unfinished_or_dry_run_sessions() {
+        let path = create_testing_files(&[], FILES);
+        let cache_dir = PathBuf::from(format!("{path}/cache"));
+
+        let mut app = setup_app(&path, InitConfig::default());
+        app.init();
+        app.save_cache().unwrap();
+        assert!(!cache_dir.exists());
+
+        let mut app = setup_app(
+            &path,
+            InitConfig {
+                dry_run: Some(true),
+                ..InitConfig::default()
+            },
+        );
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        app.save_cache().unwrap();
+        assert!(!cache_dir.exists());
+        assert!(!app.load_cache());
+    }
+
+    #[test]
+    fn writes_cache_per_root() {
+        let path = create_testing_files(&[], FILES);
+        scan_and_save(&path);
+
+        let mut app = setup_app(&path, InitConfig::default());
+        let root = app.store.get_current_path().clone();
+        let cache = ScanCache::read(&cache_file_path(
+            &PathBuf::from(format!("{path}/cache")),
+            &root,
+        ))
+        .unwrap();
+        assert_eq!(cache.key.root, root);
+        assert_eq!(cache.folders.len(), 3);
+        assert!(cache.folders.iter().all(|folder| folder.modified.is_some()));
+        assert_ne!(
+            cache_file_path(&PathBuf::from("cache"), &root),
+            cache_file_path(&PathBuf::from("cache"), &root.join("src"))
+        );
+    }
+}
@@ -0,0 +1,144 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod scan_errors {
+
+    use wiper::config::InitConfig;
+    use wiper::fs::{DataStore, DataStoreType, ScanError};
+
+    use super::*;
+    use std::fs;
+    use std::io::ErrorKind;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - readable
+    ///     - file.txt (100 bytes)
+    /// - sub
+    ///     - file.txt (100 bytes)
+    const FILES: &[(&str, u64)] = &[
+        ("readable/file.txt", TEST_FILE_SIZE),
+        ("sub/file.txt", TEST_FILE_SIZE),
+    ];
+
+    fn missing_root() -> InitConfig {
+        InitConfig {
+            file_path: Some(format!("{TEST_FILE_PATH_EDIT}_errors_missing")),
+            ..InitConfig::default()
+        }
+    }
+
+    #[test]
+    fn collects_error_of_missing_root() {
+        let app: App<DataStoreType> = setup_app_with(missing_root());
+
+        let errors = app.store.get_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].path.ends_with("edit_errors_missing"));
+        assert_eq!(errors[0].kind, ErrorKind::NotFound);
+        assert!(!errors[0].message.is_empty());
+    }
+
+    #[test]
+    fn collects_error_of_unreadable_parent() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(InitConfig {
+            file_path: Some(format!("{path}/sub")),
+            ..InitConfig::default()
+        });
+        assert!(app.store.get_errors().is_empty());
+
+        fs::remove_dir_all(&*path).expect("Failed to remove test folder");
+        app.on_backspace();
+        handle_tasks_synchronously(&mut app);
+
+        let errors = app.store.get_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].path.ends_with(path.trim_start_matches("./")));
+        assert_eq!(errors[0].kind, ErrorKind::NotFound);
+        assert!(get_current_folder(&app).unwrap().has_error);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn marks_unreadable_folder() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = create_testing_files(&[], FILES);
+        let locked = format!("{path}/sub");
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o000))
+            .expect("Failed to change permissions");
+        if fs::read_dir(&locked).is_ok() {
+            // Permissions are not enforced, e.g. for root
+            fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+            return;
+        }
+
+        let app: App<DataStoreType> = setup_app_with(path.config());
+
+        let errors = app.store.get_errors();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].path.ends_with("sub"));
+        assert_eq!(errors[0].kind, ErrorKind::PermissionDenied);
+        assert!(get_entry_by_title(&app, "sub").has_error);
+        assert!(!get_entry_by_title(&app, "readable").has_error);
+        assert!(!get_current_folder(&app).unwrap().has_error);
+
+        fs::set_permissions(&locked, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn clears_errors_on_reset() {
+        let path = create_testing_files(&[], FILES);
+        let root = format!("{path}/root");
+        let mut app: App<DataStoreType> = setup_app_with(InitConfig {
+            file_path: Some(root.clone()),
+            ..InitConfig::default()
+        });
+        assert_eq!(app.store.get_errors().len(), 1);
+
+        fs::create_dir(root).expect("Failed to create test folder");
+        app.reset();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(app.store.get_errors().is_empty());
+    }
+
+    #[test]
+    fn toggles_error_list() {
+        let mut app: App<DataStoreType> = start_app_with(missing_root());
+
+        app.on_toggle_errors();
+        assert!(app.ui_config.show_errors);
+        app.on_toggle_errors();
+        assert!(!app.ui_config.show_errors);
+        app.on_toggle_errors();
+        app.on_escape();
+        assert!(!app.ui_config.show_errors);
+    }
+
+    #[test]
+    fn moves_cursor_in_error_list() {
+        let mut app: App<DataStoreType> = setup_app_with(missing_root());
+        let root = app.store.get_current_path().clone();
+        for title in ["a", "b", "a"] {
+            let err = std::io::Error::from(ErrorKind::PermissionDenied);
+            app.store.add_error(ScanError::new(&root.join(title), &err));
+        }
+        // Error of missing root and two distinct paths
+        assert_eq!(app.store.get_errors().len(), 3);
+
+        app.on_toggle_errors();
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_cursor_down();
+        assert_eq!(app.errors_cursor, 2);
+        app.on_cursor_up();
+        assert_eq!(app.errors_cursor, 1);
+
+        app.on_toggle_errors();
+        app.on_toggle_errors();
+        assert_eq!(app.errors_cursor, 0);
+    }
+}
@@ -0,0 +1,75 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod scanner {
+
+    use wiper::fs::{DataStore, DataStoreType, ExcludeFilter, ScanOptions};
+    use wiper::scanner::Scanner;
+
+    use super::*;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - node_modules
+    ///     - dep.js
+    /// - src
+    ///     - main.rs
+    /// - notes.txt
+    const FILES: &[(&str, u64)] = &[
+        ("node_modules/dep.js", TEST_FILE_SIZE),
+        ("src/main.rs", TEST_FILE_SIZE),
+        ("notes.txt", TEST_FILE_SIZE),
+    ];
+
+    #[test]
+    fn scans_same_tree_as_app() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        handle_tasks_synchronously(&mut app);
+        let mut tree = Scanner::new(TEST_FILE_PATH_VIEW).run().unwrap();
+
+        let root = app.store.get_current_path().clone();
+        assert_eq!(tree.root, root);
+        assert_eq!(tree.size(), get_current_folder(&app).unwrap().get_size());
+        let mut keys = tree.store.get_keys();
+        let mut app_keys = app.store.get_keys();
+        keys.sort();
+        app_keys.sort();
+        assert_eq!(keys, app_keys);
+        assert!(tree.errors().is_empty());
+    }
+
+    #[test]
+    fn applies_scan_options() {
+        let path = create_testing_files(&[], FILES);
+        let tree = Scanner::new(&*path)
+            .options(ScanOptions {
+                exclude: ExcludeFilter::new(&["node_modules".to_string()]).unwrap(),
+                ..ScanOptions::default()
+            })
+            .run()
+            .unwrap();
+
+        assert_eq!(tree.size(), TEST_FILE_SIZE * 2);
+        assert!(tree.folder(&tree.root.join("src")).is_some());
+        let root = tree.folder(&tree.root).unwrap();
+        assert!(root
+            .entries
+            .iter()
+            .any(|entry| entry.title == "node_modules" && entry.is_excluded));
+    }
+
+    #[test]
+    fn reports_progress() {
+        let path = create_testing_files(&[], FILES);
+        let mut calls = 0;
+        let tree = Scanner::new(&*path)
+            .run_into(DataStoreType::new(), |_| calls += 1)
+            .unwrap();
+
+        assert!(calls > 0);
+        let total = tree.stats.total();
+        assert_eq!(total.files, 3);
+        assert_eq!(total.bytes, TEST_FILE_SIZE * 3);
+    }
+}
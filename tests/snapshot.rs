@@ -0,0 +1,267 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod snapshot {
+
+    use wiper::config::InitConfig;
+    use wiper::fs::{DataStore, DataStoreType, SizeChangeKind, SizeMode, SortBy};
+    use wiper::snapshot::{diff_snapshots, Snapshot, SnapshotEntry};
+
+    use ratatui::{buffer::Buffer, layout::Rect, widgets::Widget};
+
+    use super::*;
+    use std::fs::{self, File};
+    use std::path::{Path, PathBuf};
+    use std::process::Command;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - scan
+    ///     - cache
+    ///         - blob.bin
+    ///     - src
+    ///         - main.rs
+    ///     - notes.txt
+    const FILES: &[(&str, u64)] = &[
+        ("scan/cache/blob.bin", TEST_FILE_SIZE),
+        ("scan/src/main.rs", TEST_FILE_SIZE),
+        ("scan/notes.txt", TEST_FILE_SIZE),
+    ];
+
+    fn take_snapshot(path: &str) -> Snapshot {
+        let c = InitConfig {
+            file_path: Some(format!("{path}/scan")),
+            ..InitConfig::default()
+        };
+        let mut app: App<DataStoreType> = App::new(c).unwrap();
+        app.init();
+        handle_tasks_synchronously(&mut app);
+        let root = app.store.get_current_path().clone();
+        Snapshot::from_store(&app.store, &root, &app.ui_config.size_mode)
+    }
+
+    fn save_snapshot(path: &str, name: &str) -> String {
+        let snapshot_path = format!("{path}/{name}.json");
+        take_snapshot(path)
+            .write(File::create(&snapshot_path).unwrap())
+            .unwrap();
+        snapshot_path
+    }
+
+    /// - notes.txt grows, src/main.rs shrinks, cache is removed, src/lib.rs is added
+    fn change_testing_files(path: &str) {
+        write_file(path, "scan/notes.txt", TEST_FILE_SIZE * 3);
+        write_file(path, "scan/src/main.rs", TEST_FILE_SIZE / 2);
+        write_file(path, "scan/src/lib.rs", TEST_FILE_SIZE);
+        fs::remove_dir_all(format!("{path}/scan/cache")).unwrap();
+    }
+
+    fn setup_app_diff(old_path: &str, new_path: &str) -> App<DataStoreType> {
+        let c = InitConfig {
+            diff: Some(vec![PathBuf::from(old_path), PathBuf::from(new_path)]),
+            ..InitConfig::default()
+        };
+        let mut app: App<DataStoreType> = App::new(c).unwrap();
+        app.open_diff(Path::new(old_path), Path::new(new_path))
+            .unwrap();
+        app
+    }
+
+    #[test]
+    fn snapshots_completed_scan() {
+        let path = create_testing_files(&[], FILES);
+        let snapshot = take_snapshot(&path);
+
+        assert!(snapshot.root.is_absolute());
+        assert!(snapshot.root.ends_with("scan"));
+        assert_eq!(snapshot.size_mode, SizeMode::Apparent);
+        assert_eq!(
+            snapshot.entries,
+            vec![
+                SnapshotEntry {
+                    path: PathBuf::from("cache"),
+                    size: TEST_FILE_SIZE,
+                    is_folder: true,
+                },
+                SnapshotEntry {
+                    path: PathBuf::from("cache/blob.bin"),
+                    size: TEST_FILE_SIZE,
+                    is_folder: false,
+                },
+                SnapshotEntry {
+                    path: PathBuf::from("src"),
+                    size: TEST_FILE_SIZE,
+                    is_folder: true,
+                },
+                SnapshotEntry {
+                    path: PathBuf::from("src/main.rs"),
+                    size: TEST_FILE_SIZE,
+                    is_folder: false,
+                },
+                SnapshotEntry {
+                    path: PathBuf::from("notes.txt"),
+                    size: TEST_FILE_SIZE,
+                    is_folder: false,
+                },
+            ]
+        );
+
+        let snapshot_path = save_snapshot(&path, "old");
+        assert_eq!(
+            Snapshot::read(Path::new(&snapshot_path)).unwrap(),
+            Snapshot {
+                timestamp: Snapshot::read(Path::new(&snapshot_path)).unwrap().timestamp,
+                ..snapshot
+            }
+        );
+    }
+
+    #[test]
+    fn shows_changes_between_snapshots() {
+        let path = create_testing_files(&[], FILES);
+        let old_path = save_snapshot(&path, "old");
+        change_testing_files(&path);
+        let new_path = save_snapshot(&path, "new");
+        let mut app = setup_app_diff(&old_path, &new_path);
+
+        assert!(app.ui_config.read_only);
+        assert!(app.ui_config.diff);
+        assert_eq!(app.ui_config.sort_by, SortBy::Delta);
+        assert!(app.store.get_current_path().ends_with("scan"));
+        let root = get_current_folder(&app).unwrap();
+        assert_eq!(
+            root.get_size(),
+            TEST_FILE_SIZE * 3 + TEST_FILE_SIZE / 2 + TEST_FILE_SIZE
+        );
+        assert_eq!(root.get_delta(), TEST_FILE_SIZE as i64 * 3 / 2);
+
+        let notes = get_entry_by_title(&app, "notes.txt").change.unwrap();
+        assert_eq!(notes.kind, SizeChangeKind::Grown);
+        assert_eq!(notes.delta, TEST_FILE_SIZE as i64 * 2);
+        let cache = get_entry_by_title(&app, "cache");
+        assert_eq!(cache.size, None);
+        assert_eq!(cache.change.unwrap().kind, SizeChangeKind::Removed);
+        assert_eq!(cache.change.unwrap().delta, -(TEST_FILE_SIZE as i64));
+        let src = get_entry_by_title(&app, "src").change.unwrap();
+        assert_eq!(src.kind, SizeChangeKind::Grown);
+        assert_eq!(src.delta, TEST_FILE_SIZE as i64 / 2);
+
+        app.pre_render();
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_enter();
+        assert!(app.store.get_current_path().ends_with("scan/src"));
+        assert_eq!(
+            get_entry_by_title(&app, "lib.rs").change.unwrap().kind,
+            SizeChangeKind::Added
+        );
+        assert_eq!(
+            get_entry_by_title(&app, "main.rs").change.unwrap().kind,
+            SizeChangeKind::Shrunk
+        );
+    }
+
+    #[test]
+    fn sorts_by_delta() {
+        let path = create_testing_files(&[], FILES);
+        let old_path = save_snapshot(&path, "old");
+        change_testing_files(&path);
+        let new_path = save_snapshot(&path, "new");
+        let mut app = setup_app_diff(&old_path, &new_path);
+        app.pre_render();
+
+        let titles: Vec<String> = get_current_folder(&app)
+            .unwrap()
+            .entries
+            .iter()
+            .map(|entry| entry.title.clone())
+            .collect();
+        assert_eq!(titles, vec!["..", "notes.txt", "src", "cache"]);
+
+        app.on_toggle_sorting();
+        assert_eq!(app.ui_config.sort_by, SortBy::Title);
+        app.on_toggle_sorting();
+        assert_eq!(app.ui_config.sort_by, SortBy::Size);
+        app.on_toggle_sorting();
+        assert_eq!(app.ui_config.sort_by, SortBy::Delta);
+    }
+
+    #[test]
+    fn does_not_sort_by_delta_outside_of_diff() {
+        let mut app: App<DataStoreType> = setup_app_view();
+        app.on_toggle_sorting();
+        app.on_toggle_sorting();
+        app.on_toggle_sorting();
+        assert_eq!(app.ui_config.sort_by, SortBy::Size);
+    }
+
+    #[test]
+    fn refuses_snapshots_of_different_size_modes() {
+        let path = create_testing_files(&[], FILES);
+        let old = take_snapshot(&path);
+        let new = Snapshot {
+            size_mode: SizeMode::Allocated,
+            ..old.clone()
+        };
+        let mut store = DataStoreType::new();
+
+        let err = diff_snapshots(&old, &new, &mut store).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn binary_writes_snapshot() {
+        let path = create_testing_files(&[], FILES);
+        let snapshot_path = format!("{path}/old.json");
+
+        let output = Command::new(env!("CARGO_BIN_EXE_wiper"))
+            .args(["--export", "snapshot", "-o", &snapshot_path])
+            .arg(format!("{path}/scan"))
+            .output()
+            .unwrap();
+        assert!(output.status.success());
+        let snapshot = Snapshot::read(Path::new(&snapshot_path)).unwrap();
+        assert_eq!(snapshot.entries.len(), 5);
+
+        let output = Command::new(env!("CARGO_BIN_EXE_wiper"))
+            .args(["--diff", &snapshot_path, &snapshot_path, "./"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(2));
+    }
+
+    #[test]
+    fn renders_delta_after_ignored_column_in_git_mode() {
+        let path = create_testing_files(&[], FILES);
+        let old_path = save_snapshot(&path, "old");
+        change_testing_files(&path);
+        let new_path = save_snapshot(&path, "new");
+        let mut app = setup_app_diff(&old_path, &new_path);
+        app.ui_config.git = true;
+        app.pre_render();
+
+        let area = Rect::new(0, 0, 160, 20);
+        let mut buf = Buffer::empty(area);
+        (&mut app).render(area, &mut buf);
+        let lines: Vec<String> = (0..area.height)
+            .map(|y| {
+                (0..area.width)
+                    .map(|x| buf.get(x, y).symbol())
+                    .collect::<String>()
+            })
+            .collect();
+
+        let column =
+            |line: &String, pattern: &str| line[..line.find(pattern).unwrap()].chars().count();
+
+        let header = lines.iter().find(|line| line.contains("Ignored")).unwrap();
+        let delta_column = column(header, "Delta");
+        assert!(column(header, "Ignored") < delta_column);
+        let notes = lines
+            .iter()
+            .find(|line| line.contains("notes.txt"))
+            .unwrap();
+        assert!(column(notes, "+") >= delta_column);
+    }
+}
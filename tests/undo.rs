@@ -0,0 +1,161 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod undo {
+
+    use wiper::config::InitConfig;
+    use wiper::fs::{DataStoreType, FolderEntryType};
+
+    use super::*;
+    use std::path::Path;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - folder
+    ///     - file_1.txt .. file_3.txt (100 bytes each)
+    /// - file_1.txt (100 bytes)
+    /// - file_2.txt (100 bytes)
+    const FILES: &[(&str, u64)] = &[
+        ("folder/file_1.txt", TEST_FILE_SIZE),
+        ("folder/file_2.txt", TEST_FILE_SIZE),
+        ("folder/file_3.txt", TEST_FILE_SIZE),
+        ("file_1.txt", TEST_FILE_SIZE),
+        ("file_2.txt", TEST_FILE_SIZE),
+    ];
+
+    fn setup_app_trash(path: &TestFiles) -> App<DataStoreType> {
+        setup_app_with(InitConfig {
+            move_to_trash: Some(true),
+            ..path.config()
+        })
+    }
+
+    fn move_cursor_to(app: &mut App<DataStoreType>, index: usize) {
+        while get_current_folder(app).unwrap().cursor_index > index {
+            app.on_cursor_up();
+        }
+        while get_current_folder(app).unwrap().cursor_index < index {
+            app.on_cursor_down();
+        }
+    }
+
+    /// Deletes entry at provided index, returns `false` if trash is not available
+    fn delete_entry_at(app: &mut App<DataStoreType>, index: usize) -> bool {
+        move_cursor_to(app, index);
+        app.on_delete();
+        app.on_delete();
+        handle_tasks_synchronously(app);
+        if !app.delete_failures.is_empty() {
+            eprintln!("Trash is not available: {:?}", app.delete_failures);
+            return false;
+        }
+        true
+    }
+
+    #[test]
+    fn restores_trashed_file() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app_trash(&path);
+
+        if !delete_entry_at(&mut app, 2) {
+            return;
+        }
+        assert!(!Path::new(&format!("{path}/file_1.txt")).exists());
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 1);
+        assert_eq!(
+            get_current_folder(&app).unwrap().file_type_map["txt"],
+            TEST_FILE_SIZE * 4
+        );
+
+        app.on_undo();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(Path::new(&format!("{path}/file_1.txt")).exists());
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 2);
+        assert_eq!(
+            get_entry_by_title(&app, "file_1.txt").size,
+            Some(TEST_FILE_SIZE)
+        );
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 5
+        );
+        assert_eq!(
+            get_current_folder(&app).unwrap().file_type_map["txt"],
+            TEST_FILE_SIZE * 5
+        );
+        assert!(app.deletion_history.is_empty());
+    }
+
+    #[test]
+    fn restores_trashed_batch_with_folder() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app_trash(&path);
+
+        app.on_cursor_down();
+        app.on_toggle_mark();
+        app.on_toggle_mark();
+        if !delete_entry_at(&mut app, 1) {
+            return;
+        }
+        assert_eq!(app.deletion_history.len(), 1);
+        assert_eq!(app.deletion_history[0].len(), 2);
+        assert_eq!(get_current_folder(&app).unwrap().get_size(), TEST_FILE_SIZE);
+
+        app.on_undo();
+        handle_tasks_synchronously(&mut app);
+
+        assert!(Path::new(&format!("{path}/folder/file_3.txt")).exists());
+        assert_eq!(
+            get_entry_by_title(&app, "folder").size,
+            Some(TEST_FILE_SIZE * 3)
+        );
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 5
+        );
+
+        move_cursor_to(&mut app, 1);
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 3);
+    }
+
+    #[test]
+    fn restores_batches_in_reverse_order() {
+        let path = create_testing_files(&[], FILES);
+        let mut app = setup_app_trash(&path);
+
+        if !delete_entry_at(&mut app, 3) || !delete_entry_at(&mut app, 2) {
+            return;
+        }
+        assert_eq!(app.deletion_history.len(), 2);
+
+        app.on_undo();
+        handle_tasks_synchronously(&mut app);
+        assert!(Path::new(&format!("{path}/file_1.txt")).exists());
+        assert!(!Path::new(&format!("{path}/file_2.txt")).exists());
+
+        app.on_undo();
+        handle_tasks_synchronously(&mut app);
+        assert!(Path::new(&format!("{path}/file_2.txt")).exists());
+        assert_eq!(
+            get_current_folder(&app).unwrap().get_size(),
+            TEST_FILE_SIZE * 5
+        );
+    }
+
+    #[test]
+    fn does_not_record_permanent_deletions() {
+        let path = create_testing_files(&[], FILES);
+        let mut app: App<DataStoreType> = setup_app_with(path.config());
+
+        assert!(delete_entry_at(&mut app, 2));
+        assert!(app.deletion_history.is_empty());
+
+        app.on_undo();
+        handle_tasks_synchronously(&mut app);
+        assert_eq!(get_entry_by_kind(&app, FolderEntryType::File).len(), 1);
+    }
+}
@@ -0,0 +1,266 @@
+pub mod common;
+use crate::common::*;
+use wiper::app::App;
+
+mod watch {
+
+    use wiper::config::InitConfig;
+    use wiper::fs::{DataStore, DataStoreType, FolderEntryType, SortBy};
+
+    use super::*;
+    use std::fs;
+    use std::path::PathBuf;
+
+    const TEST_FILE_SIZE: u64 = 100;
+
+    /// - target
+    ///     - debug
+    ///         - app.bin
+    /// - node_modules
+    /// - notes.txt
+    const FOLDERS: &[&str] = &["node_modules"];
+    const FILES: &[(&str, u64)] = &[
+        ("target/debug/app.bin", TEST_FILE_SIZE),
+        ("notes.txt", TEST_FILE_SIZE),
+    ];
+
+    fn setup_app(path: &str) -> App<DataStoreType> {
+        setup_app_with(InitConfig {
+            file_path: Some(path.to_string()),
+            exclude: vec!["node_modules".into()],
+            ..InitConfig::default()
+        })
+    }
+
+    fn root_path(app: &mut App<DataStoreType>) -> PathBuf {
+        app.store.get_current_path().clone()
+    }
+
+    fn sync(app: &mut App<DataStoreType>, paths: &[&str]) {
+        let root = root_path(app);
+        app.sync_paths(paths.iter().map(|path| root.join(path)).collect());
+        handle_tasks_synchronously(app);
+    }
+
+    fn folder_size(app: &mut App<DataStoreType>, relative_path: &str) -> u64 {
+        let path = root_path(app).join(relative_path);
+        app.store.get_folder(&path).unwrap().get_size()
+    }
+
+    #[test]
+    fn updates_modified_file_and_ancestors() {
+        let path = create_testing_files(FOLDERS, FILES);
+        let mut app = setup_app(&path);
+
+        write_file(&path, "target/debug/app.bin", TEST_FILE_SIZE * 3);
+        sync(&mut app, &["target/debug/app.bin"]);
+
+        assert_eq!(folder_size(&mut app, "target/debug"), TEST_FILE_SIZE * 3);
+        assert_eq!(
+            get_entry_by_title(&app, "target").size,
+            Some(TEST_FILE_SIZE * 3)
+        );
+        assert_eq!(folder_size(&mut app, ""), TEST_FILE_SIZE * 4);
+        let root = get_current_folder(&app).unwrap();
+        assert_eq!(root.file_type_map["bin"], TEST_FILE_SIZE * 3);
+        assert_eq!(root.file_type_map["txt"], TEST_FILE_SIZE);
+    }
+
+    #[test]
+    fn adds_created_entries() {
+        let path = create_testing_files(FOLDERS, FILES);
+        let mut app = setup_app(&path);
+
+        write_file(&path, "target/debug/lib.rlib", TEST_FILE_SIZE);
+        fs::create_dir_all(format!("{path}/target/release/deps")).unwrap();
+        write_file(&path, "target/release/deps/app.bin", TEST_FILE_SIZE * 2);
+        sync(
+            &mut app,
+            &[
+                "target/debug/lib.rlib",
+                "target/release",
+                "target/release/deps",
+                "target/release/deps/app.bin",
+            ],
+        );
+
+        assert_eq!(folder_size(&mut app, "target/debug"), TEST_FILE_SIZE * 2);
+        assert_eq!(folder_size(&mut app, "target/release"), TEST_FILE_SIZE * 2);
+        assert_eq!(folder_size(&mut app, "target"), TEST_FILE_SIZE * 4);
+        assert_eq!(folder_size(&mut app, ""), TEST_FILE_SIZE * 5);
+
+        // Repeated events change nothing
+        sync(&mut app, &["target/release", "target/debug/lib.rlib"]);
+        assert_eq!(folder_size(&mut app, ""), TEST_FILE_SIZE * 5);
+    }
+
+    #[test]
+    fn removes_deleted_entries() {
+        let path = create_testing_files(FOLDERS, FILES);
+        let mut app = setup_app(&path);
+
+        fs::remove_dir_all(format!("{path}/target")).unwrap();
+        sync(
+            &mut app,
+            &["target", "target/debug", "target/debug/app.bin"],
+        );
+
+        assert_eq!(folder_size(&mut app, ""), TEST_FILE_SIZE);
+        let titles: Vec<String> = get_current_folder(&app)
+            .unwrap()
+            .entries
+            .iter()
+            .map(|entry| entry.title.clone())
+            .collect();
+        assert_eq!(titles, vec!["..", "node_modules", "notes.txt"]);
+        assert!(!get_current_folder(&app)
+            .unwrap()
+            .file_type_map
+            .contains_key("bin"));
+        let target = root_path(&mut app).join("target");
+        assert!(!app.store.has_path(&target));
+    }
+
+    #[test]
+    fn follows_moved_entries() {
+        let path = create_testing_files(FOLDERS, FILES);
+        let mut app = setup_app(&path);
+
+        fs::rename(
+            format!("{path}/notes.txt"),
+            format!("{path}/target/notes.txt"),
+        )
+        .unwrap();
+        sync(&mut app, &["notes.txt", "target/notes.txt"]);
+
+        assert_eq!(folder_size(&mut app, "target"), TEST_FILE_SIZE * 2);
+        assert_eq!(folder_size(&mut app, ""), TEST_FILE_SIZE * 2);
+
+        // File replaced with a folder
+        fs::remove_file(format!("{path}/target/notes.txt")).unwrap();
+        fs::create_dir(format!("{path}/target/notes.txt")).unwrap();
+        write_file(&path, "target/notes.txt/today.md", TEST_FILE_SIZE * 2);
+        sync(&mut app, &["target/notes.txt"]);
+
+        app.on_cursor_down();
+        app.on_cursor_down();
+        app.on_enter();
+        handle_tasks_synchronously(&mut app);
+        assert_item_at_index_is(&app, 2, FolderEntryType::Folder);
+        assert_item_at_index_title(&app, 2, "notes.txt".into());
+        assert_eq!(folder_size(&mut app, ""), TEST_FILE_SIZE * 3);
+    }
+
+    #[test]
+    fn skips_excluded_and_unknown_paths() {
+        let path = create_testing_files(FOLDERS, FILES);
+        let mut app = setup_app(&path);
+
+        write_file(&path, "node_modules/dep.js", TEST_FILE_SIZE);
+        fs::create_dir_all(format!("{path}/target/debug/node_modules")).unwrap();
+        sync(
+            &mut app,
+            &[
+                "node_modules/dep.js",
+                "target/debug/node_modules",
+                "missing/file.txt",
+            ],
+        );
+
+        assert_eq!(get_entry_by_title(&app, "node_modules").size, None);
+        assert_eq!(folder_size(&mut app, ""), TEST_FILE_SIZE * 2);
+        let debug = root_path(&mut app).join("target/debug");
+        let excluded = app
+            .store
+            .get_folder(&debug)
+            .unwrap()
+            .entries
+            .iter()
+            .find(|entry| entry.title == "node_modules")
+            .cloned()
+            .unwrap();
+        assert!(excluded.is_excluded);
+    }
+
+    #[test]
+    fn counts_created_hard_link_once() {
+        let path = create_testing_files(FOLDERS, FILES);
+        fs::hard_link(format!("{path}/notes.txt"), format!("{path}/copy.txt")).unwrap();
+        let mut app = setup_app(&path);
+
+        fs::hard_link(
+            format!("{path}/notes.txt"),
+            format!("{path}/target/link.txt"),
+        )
+        .unwrap();
+        sync(&mut app, &["target/link.txt"]);
+
+        assert_eq!(folder_size(&mut app, ""), TEST_FILE_SIZE * 2);
+        assert_eq!(
+            get_entry_by_title(&app, "target").size,
+            Some(TEST_FILE_SIZE)
+        );
+        let root = root_path(&mut app);
+        assert_eq!(
+            app.store
+                .get_folder(&root.join("target"))
+                .unwrap()
+                .hard_links,
+            1
+        );
+        assert_eq!(get_current_folder(&app).unwrap().hard_links, 2);
+
+        // Both paths of a file which gets linked while watching are reported
+        fs::hard_link(
+            format!("{path}/target/debug/app.bin"),
+            format!("{path}/app.bin"),
+        )
+        .unwrap();
+        sync(&mut app, &["app.bin", "target/debug/app.bin"]);
+        assert_eq!(folder_size(&mut app, ""), TEST_FILE_SIZE * 2);
+        assert_eq!(get_current_folder(&app).unwrap().hard_links, 3);
+    }
+
+    #[test]
+    fn classifies_created_file_in_git_mode() {
+        let path = create_testing_files(&[".git"], FILES);
+        fs::write(format!("{path}/.gitignore"), "*.log\n").unwrap();
+        let c = InitConfig {
+            file_path: Some(path.to_string()),
+            git: Some(true),
+            ..InitConfig::default()
+        };
+        let mut app: App<DataStoreType> = App::new(c).unwrap();
+        app.ui_config.sort_by = SortBy::Title;
+        app.init();
+        handle_tasks_synchronously(&mut app);
+
+        write_file(&path, "target/debug/trace.log", TEST_FILE_SIZE);
+        sync(&mut app, &["target/debug/trace.log"]);
+
+        assert_eq!(
+            get_entry_by_title(&app, "target").ignored_size,
+            TEST_FILE_SIZE
+        );
+    }
+
+    #[cfg(feature = "watch")]
+    #[test]
+    fn watches_changes_on_disk() {
+        use std::thread;
+        use std::time::{Duration, Instant};
+
+        let path = create_testing_files(FOLDERS, FILES);
+        let mut app = setup_app(&path);
+        app.watch().unwrap();
+
+        write_file(&path, "target/debug/lib.rlib", TEST_FILE_SIZE * 2);
+        let started_at = Instant::now();
+        while folder_size(&mut app, "") != TEST_FILE_SIZE * 4 {
+            assert!(started_at.elapsed() < Duration::from_secs(5));
+            app.tick();
+            thread::sleep(Duration::from_millis(10));
+        }
+        assert_eq!(folder_size(&mut app, "target/debug"), TEST_FILE_SIZE * 3);
+    }
+}